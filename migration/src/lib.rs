@@ -1,4 +1,23 @@
 mod m20250709_162257_create_articles_table;
+mod m20261018_090000_add_article_metadata;
+mod m20261018_100000_create_tags_table;
+mod m20261018_100100_create_article_tags_table;
+mod m20261018_110000_add_article_search;
+mod m20261018_120000_add_articles_created_at_index;
+mod m20261018_130000_add_articles_normalized_url;
+mod m20261018_140000_add_articles_deleted_at;
+mod m20261018_150000_add_articles_read_at;
+mod m20261018_160000_create_feeds_table;
+mod m20261018_170000_create_jobs_table;
+mod m20261018_180000_create_images_table;
+mod m20261018_190000_create_article_contents_table;
+mod m20261018_200000_create_snapshots_table;
+mod m20261018_210000_create_link_checks_table;
+mod m20261018_220000_add_articles_reading_time;
+mod m20261018_230000_add_articles_original_url;
+mod m20261019_000000_create_tag_rules_table;
+mod m20261019_010000_renormalize_articles_urls;
+mod m20261019_020000_backfill_articles_reading_time;
 
 pub use sea_orm_migration::prelude::*;
 
@@ -7,6 +26,27 @@ pub struct Migrator;
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20250709_162257_create_articles_table::Migration)]
+        vec![
+            Box::new(m20250709_162257_create_articles_table::Migration),
+            Box::new(m20261018_090000_add_article_metadata::Migration),
+            Box::new(m20261018_100000_create_tags_table::Migration),
+            Box::new(m20261018_100100_create_article_tags_table::Migration),
+            Box::new(m20261018_110000_add_article_search::Migration),
+            Box::new(m20261018_120000_add_articles_created_at_index::Migration),
+            Box::new(m20261018_130000_add_articles_normalized_url::Migration),
+            Box::new(m20261018_140000_add_articles_deleted_at::Migration),
+            Box::new(m20261018_150000_add_articles_read_at::Migration),
+            Box::new(m20261018_160000_create_feeds_table::Migration),
+            Box::new(m20261018_170000_create_jobs_table::Migration),
+            Box::new(m20261018_180000_create_images_table::Migration),
+            Box::new(m20261018_190000_create_article_contents_table::Migration),
+            Box::new(m20261018_200000_create_snapshots_table::Migration),
+            Box::new(m20261018_210000_create_link_checks_table::Migration),
+            Box::new(m20261018_220000_add_articles_reading_time::Migration),
+            Box::new(m20261018_230000_add_articles_original_url::Migration),
+            Box::new(m20261019_000000_create_tag_rules_table::Migration),
+            Box::new(m20261019_010000_renormalize_articles_urls::Migration),
+            Box::new(m20261019_020000_backfill_articles_reading_time::Migration),
+        ]
     }
 }
@@ -0,0 +1,51 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // existing rows get the time of the migration as their timestamps
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Articles::Table)
+                    .add_column(
+                        timestamp_with_time_zone(Articles::CreatedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .add_column(
+                        timestamp_with_time_zone(Articles::UpdatedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .add_column(text_null(Articles::Description))
+                    .add_column(string_null(Articles::FoundVia))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Articles::Table)
+                    .drop_column(Articles::CreatedAt)
+                    .drop_column(Articles::UpdatedAt)
+                    .drop_column(Articles::Description)
+                    .drop_column(Articles::FoundVia)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    CreatedAt,
+    UpdatedAt,
+    Description,
+    FoundVia,
+}
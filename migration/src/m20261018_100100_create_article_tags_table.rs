@@ -0,0 +1,62 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArticleTags::Table)
+                    .if_not_exists()
+                    .col(uuid(ArticleTags::ArticleUuid))
+                    .col(uuid(ArticleTags::TagUuid))
+                    .primary_key(
+                        Index::create()
+                            .col(ArticleTags::ArticleUuid)
+                            .col(ArticleTags::TagUuid),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ArticleTags::Table, ArticleTags::ArticleUuid)
+                            .to(Articles::Table, Articles::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ArticleTags::Table, ArticleTags::TagUuid)
+                            .to(Tags::Table, Tags::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ArticleTags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ArticleTags {
+    Table,
+    ArticleUuid,
+    TagUuid,
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+    Table,
+    Uuid,
+}
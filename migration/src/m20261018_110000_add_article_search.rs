@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // generated columns can't be expressed through sea-query, so the
+        // column is created by hand. Titles and descriptions are stemmed,
+        // urls are only split into their words.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE articles ADD COLUMN search_vector tsvector
+                GENERATED ALWAYS AS (
+                    setweight(to_tsvector('english', coalesce(title, '')), 'A') ||
+                    setweight(to_tsvector('english', coalesce(description, '')), 'B') ||
+                    setweight(to_tsvector('simple', coalesce(url, '')), 'C')
+                ) STORED",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_articles_search_vector")
+                    .table(Articles::Table)
+                    .col(Articles::SearchVector)
+                    // postgres creates full text indexes as GIN indexes
+                    .full_text()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_articles_search_vector")
+                    .table(Articles::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Articles::Table)
+                    .drop_column(Articles::SearchVector)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    SearchVector,
+}
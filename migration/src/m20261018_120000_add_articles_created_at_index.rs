@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // matches the ordering used by the keyset pagination of the list
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_articles_created_at_uuid")
+                    .table(Articles::Table)
+                    .col((Articles::CreatedAt, IndexOrder::Desc))
+                    .col((Articles::Uuid, IndexOrder::Desc))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_articles_created_at_uuid")
+                    .table(Articles::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    CreatedAt,
+    Uuid,
+}
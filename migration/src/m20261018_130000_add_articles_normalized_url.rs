@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use sea_orm_migration::{
+    prelude::*,
+    schema::*,
+    sea_orm::{prelude::Uuid, FromQueryResult},
+};
+use url::Url;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(FromQueryResult)]
+struct StoredUrl {
+    uuid: Uuid,
+    url: String,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Articles::Table)
+                    .add_column(string_null(Articles::NormalizedUrl))
+                    .to_owned(),
+            )
+            .await?;
+
+        // the normalization can't be done in SQL, so the existing rows have to
+        // be filled in from here. Duplicates that already exist keep `NULL`
+        // so the unique index can still be created.
+        let select = Query::select()
+            .columns([Articles::Uuid, Articles::Url])
+            .from(Articles::Table)
+            .order_by(Articles::CreatedAt, Order::Asc)
+            .to_owned();
+        let builder = manager.get_database_backend();
+        let stored = StoredUrl::find_by_statement(builder.build(&select))
+            .all(manager.get_connection())
+            .await?;
+
+        let mut seen = HashSet::new();
+        for StoredUrl { uuid, url } in stored {
+            let normalized = comparable_url(&url);
+            if !seen.insert(normalized.clone()) {
+                continue;
+            }
+
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(Articles::Table)
+                        .value(Articles::NormalizedUrl, normalized)
+                        .and_where(Expr::col(Articles::Uuid).eq(uuid))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_articles_normalized_url")
+                    .table(Articles::Table)
+                    .col(Articles::NormalizedUrl)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_articles_normalized_url")
+                    .table(Articles::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Articles::Table)
+                    .drop_column(Articles::NormalizedUrl)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+    Url,
+    NormalizedUrl,
+    CreatedAt,
+}
+
+/// Query parameters that only exist to track where a click came from.
+const TRACKING_PARAMS: [&str; 11] = [
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid", "ref_src",
+];
+
+/// `domain::urls::comparable_url` as it was when this migration was written.
+/// The copy keeps the migration reproducible, changed rules are applied to
+/// the stored urls by a migration of their own.
+fn comparable_url(url: &str) -> String {
+    normalize_url(url).unwrap_or_else(|| url.trim().to_owned())
+}
+
+fn normalize_url(url: &str) -> Option<String> {
+    let mut url = Url::parse(url.trim()).ok()?;
+    url.set_fragment(None);
+
+    let params = url
+        .query_pairs()
+        .filter(|(key, _)| {
+            let key = key.to_lowercase();
+            !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+        })
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+
+    if params.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    let path = url.path().trim_end_matches('/').to_owned();
+    url.set_path(&path);
+
+    let mut normalized = url.to_string();
+    if url.query().is_none() && normalized.ends_with('/') {
+        normalized.pop();
+    }
+    Some(normalized)
+}
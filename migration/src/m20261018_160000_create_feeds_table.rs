@@ -0,0 +1,47 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Feeds::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(Feeds::Uuid))
+                    .col(string_uniq(Feeds::Url))
+                    .col(string_null(Feeds::Title))
+                    // comma separated tag names
+                    .col(text(Feeds::Tags).default(""))
+                    .col(
+                        timestamp_with_time_zone(Feeds::CreatedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_with_time_zone_null(Feeds::LastPolledAt))
+                    .col(text_null(Feeds::LastError))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Feeds::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Feeds {
+    Table,
+    Uuid,
+    Url,
+    Title,
+    Tags,
+    CreatedAt,
+    LastPolledAt,
+    LastError,
+}
@@ -0,0 +1,77 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Jobs::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(Jobs::Uuid))
+                    .col(uuid(Jobs::ArticleUuid))
+                    .col(string(Jobs::Kind))
+                    .col(integer(Jobs::Attempts).default(0))
+                    .col(
+                        timestamp_with_time_zone(Jobs::RunAfter).default(Expr::current_timestamp()),
+                    )
+                    .col(text_null(Jobs::LastError))
+                    .col(timestamp_with_time_zone_null(Jobs::FailedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Jobs::Table, Jobs::ArticleUuid)
+                            .to(Articles::Table, Articles::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_jobs_run_after")
+                    .table(Jobs::Table)
+                    .col(Jobs::RunAfter)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_jobs_article_uuid")
+                    .table(Jobs::Table)
+                    .col(Jobs::ArticleUuid)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Jobs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Jobs {
+    Table,
+    Uuid,
+    ArticleUuid,
+    Kind,
+    Attempts,
+    RunAfter,
+    LastError,
+    FailedAt,
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+}
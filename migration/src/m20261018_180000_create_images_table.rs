@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Images::Table)
+                    .if_not_exists()
+                    .col(string(Images::Hash).primary_key())
+                    .col(string(Images::ContentType))
+                    .col(blob(Images::Data))
+                    .col(
+                        timestamp_with_time_zone(Images::CreatedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Articles::Table)
+                    .add_column(string_null(Articles::Favicon))
+                    .add_column(string_null(Articles::PreviewImage))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Articles::Table)
+                    .drop_column(Articles::Favicon)
+                    .drop_column(Articles::PreviewImage)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Images::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Images {
+    Table,
+    Hash,
+    ContentType,
+    Data,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Favicon,
+    PreviewImage,
+}
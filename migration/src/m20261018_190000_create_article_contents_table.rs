@@ -0,0 +1,52 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArticleContents::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(ArticleContents::ArticleUuid))
+                    .col(text(ArticleContents::Html))
+                    .col(text(ArticleContents::Text))
+                    .col(
+                        timestamp_with_time_zone(ArticleContents::ExtractedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ArticleContents::Table, ArticleContents::ArticleUuid)
+                            .to(Articles::Table, Articles::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ArticleContents::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ArticleContents {
+    Table,
+    ArticleUuid,
+    Html,
+    Text,
+    ExtractedAt,
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Snapshots::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(Snapshots::Uuid))
+                    .col(uuid(Snapshots::ArticleUuid))
+                    .col(string(Snapshots::Hash))
+                    .col(text(Snapshots::Html))
+                    .col(
+                        timestamp_with_time_zone(Snapshots::TakenAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Snapshots::Table, Snapshots::ArticleUuid)
+                            .to(Articles::Table, Articles::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_snapshots_article_uuid_taken_at")
+                    .table(Snapshots::Table)
+                    .col(Snapshots::ArticleUuid)
+                    .col(Snapshots::TakenAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Snapshots::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Snapshots {
+    Table,
+    Uuid,
+    ArticleUuid,
+    Hash,
+    Html,
+    TakenAt,
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LinkChecks::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(LinkChecks::ArticleUuid))
+                    .col(text(LinkChecks::Url))
+                    .col(small_integer_null(LinkChecks::Status))
+                    .col(text_null(LinkChecks::FinalUrl))
+                    .col(timestamp_with_time_zone(LinkChecks::CheckedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(LinkChecks::Table, LinkChecks::ArticleUuid)
+                            .to(Articles::Table, Articles::Uuid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_link_checks_checked_at")
+                    .table(LinkChecks::Table)
+                    .col(LinkChecks::CheckedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LinkChecks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LinkChecks {
+    Table,
+    ArticleUuid,
+    Url,
+    Status,
+    FinalUrl,
+    CheckedAt,
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+}
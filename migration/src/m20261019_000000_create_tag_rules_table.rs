@@ -0,0 +1,69 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// The rules most articles are tagged by anyway
+const DEFAULT_RULES: [(&str, &str, &str); 3] = [
+    ("host", "github.com", "code"),
+    ("host", "arxiv.org", "paper"),
+    ("host", "youtube.com", "video"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TagRules::Table)
+                    .if_not_exists()
+                    .col(pk_uuid(TagRules::Uuid))
+                    .col(string(TagRules::Kind))
+                    .col(text(TagRules::Pattern))
+                    // comma separated tag names
+                    .col(text(TagRules::Tags))
+                    .col(
+                        timestamp_with_time_zone(TagRules::CreatedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut insert = Query::insert()
+            .into_table(TagRules::Table)
+            .columns([
+                TagRules::Uuid,
+                TagRules::Kind,
+                TagRules::Pattern,
+                TagRules::Tags,
+            ])
+            .to_owned();
+        for (kind, pattern, tags) in DEFAULT_RULES {
+            insert.values_panic([
+                Expr::cust("gen_random_uuid()"),
+                kind.into(),
+                pattern.into(),
+                tags.into(),
+            ]);
+        }
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TagRules::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TagRules {
+    Table,
+    Uuid,
+    Kind,
+    Pattern,
+    Tags,
+    CreatedAt,
+}
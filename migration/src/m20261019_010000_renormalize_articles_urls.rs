@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{prelude::Uuid, FromQueryResult, Statement},
+};
+use url::Url;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(FromQueryResult)]
+struct StoredUrl {
+    uuid: Uuid,
+    url: String,
+    trashed: bool,
+}
+
+/// Normalizes the stored urls again with the rules of the domain since
+/// links of other schemes than `http` and `https` are rejected. Articles
+/// whose url normalizes to the same value, including those that were kept
+/// without a normalized url before, are merged into one of them: the oldest
+/// one that isn't trashed gets the tags of the others, which are moved to
+/// the trash without a normalized url like `articles_query::merge_duplicate`
+/// does. Nothing is deleted, so the duplicates can still be looked at.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let builder = manager.get_database_backend();
+
+        let select = Query::select()
+            .columns([Articles::Uuid, Articles::Url])
+            .expr_as(
+                Expr::col(Articles::DeletedAt).is_not_null(),
+                Alias::new("trashed"),
+            )
+            .from(Articles::Table)
+            .order_by(Articles::CreatedAt, Order::Asc)
+            .order_by(Articles::Uuid, Order::Asc)
+            .to_owned();
+        let stored = StoredUrl::find_by_statement(builder.build(&select))
+            .all(db)
+            .await?;
+
+        let mut groups = Vec::<(String, Vec<StoredUrl>)>::new();
+        let mut group_of = HashMap::<String, usize>::new();
+        for stored in stored {
+            let normalized = comparable_url(&stored.url);
+            match group_of.get(&normalized) {
+                Some(&i) => groups[i].1.push(stored),
+                None => {
+                    group_of.insert(normalized.clone(), groups.len());
+                    groups.push((normalized, vec![stored]));
+                }
+            }
+        }
+
+        // values are moved between rows, so they are cleared first to not
+        // collide with the unique index in between
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Articles::Table)
+                    .value(Articles::NormalizedUrl, Option::<String>::None)
+                    .to_owned(),
+            )
+            .await?;
+
+        for (normalized, mut articles) in groups {
+            let kept = articles.iter().position(|a| !a.trashed).unwrap_or(0);
+            let kept = articles.remove(kept).uuid;
+
+            for duplicate in articles {
+                db.execute(Statement::from_sql_and_values(
+                    builder,
+                    r#"INSERT INTO "article_tags" ("article_uuid", "tag_uuid")
+                    SELECT $1, "tag_uuid" FROM "article_tags" WHERE "article_uuid" = $2
+                    ON CONFLICT DO NOTHING"#,
+                    [kept.into(), duplicate.uuid.into()],
+                ))
+                .await?;
+                manager
+                    .exec_stmt(
+                        Query::update()
+                            .table(Articles::Table)
+                            .value(Articles::DeletedAt, Expr::current_timestamp())
+                            .and_where(Expr::col(Articles::Uuid).eq(duplicate.uuid))
+                            .and_where(Expr::col(Articles::DeletedAt).is_null())
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(Articles::Table)
+                        .value(Articles::NormalizedUrl, normalized)
+                        .and_where(Expr::col(Articles::Uuid).eq(kept))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // the duplicates stay in the trash and the previous normalized urls
+        // still identify the same articles
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+    Url,
+    NormalizedUrl,
+    CreatedAt,
+    DeletedAt,
+}
+
+/// Query parameters that only exist to track where a click came from.
+const TRACKING_PARAMS: [&str; 11] = [
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid", "ref_src",
+];
+
+/// `domain::urls::comparable_url` as it was when this migration was written.
+/// The copy keeps the migration reproducible, changed rules are applied to
+/// the stored urls by a migration of their own.
+fn comparable_url(url: &str) -> String {
+    normalize_url(url).unwrap_or_else(|| url.trim().to_owned())
+}
+
+fn normalize_url(url: &str) -> Option<String> {
+    let mut url = Url::parse(url.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    url.set_fragment(None);
+
+    let params = url
+        .query_pairs()
+        .filter(|(key, _)| {
+            let key = key.to_lowercase();
+            !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_str())
+        })
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+
+    if params.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    let path = url.path().trim_end_matches('/').to_owned();
+    url.set_path(&path);
+
+    let mut normalized = url.to_string();
+    if url.query().is_none() && normalized.ends_with('/') {
+        normalized.pop();
+    }
+    Some(normalized)
+}
@@ -0,0 +1,109 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{prelude::Uuid, FromQueryResult},
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(FromQueryResult)]
+struct StoredText {
+    article_uuid: Uuid,
+    text: String,
+}
+
+/// How many contents are loaded at once
+const BATCH_SIZE: u64 = 100;
+
+/// Estimates the reading time of the articles whose content was extracted
+/// before the reading time was stored along with it.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let builder = manager.get_database_backend();
+
+        let mut after = None::<Uuid>;
+        loop {
+            let mut select = Query::select()
+                .column((ArticleContents::Table, ArticleContents::ArticleUuid))
+                .column((ArticleContents::Table, ArticleContents::Text))
+                .from(ArticleContents::Table)
+                .inner_join(
+                    Articles::Table,
+                    Expr::col((Articles::Table, Articles::Uuid))
+                        .equals((ArticleContents::Table, ArticleContents::ArticleUuid)),
+                )
+                .and_where(Expr::col((Articles::Table, Articles::ReadingMinutes)).is_null())
+                .order_by(
+                    (ArticleContents::Table, ArticleContents::ArticleUuid),
+                    Order::Asc,
+                )
+                .limit(BATCH_SIZE)
+                .to_owned();
+            if let Some(after) = after {
+                select.and_where(
+                    Expr::col((ArticleContents::Table, ArticleContents::ArticleUuid)).gt(after),
+                );
+            }
+            let batch = StoredText::find_by_statement(builder.build(&select))
+                .all(db)
+                .await?;
+            let Some(last) = batch.last() else {
+                return Ok(());
+            };
+            after = Some(last.article_uuid);
+
+            for stored in batch {
+                let words = word_count(&stored.text);
+                manager
+                    .exec_stmt(
+                        Query::update()
+                            .table(Articles::Table)
+                            .value(Articles::WordCount, words)
+                            .value(Articles::ReadingMinutes, reading_minutes(words))
+                            .and_where(Expr::col(Articles::Uuid).eq(stored.article_uuid))
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // the estimates are the same ones the content jobs store
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Articles {
+    Table,
+    Uuid,
+    WordCount,
+    ReadingMinutes,
+}
+
+#[derive(DeriveIden)]
+enum ArticleContents {
+    Table,
+    ArticleUuid,
+    Text,
+}
+
+/// `domain::reading_time::WORDS_PER_MINUTE` when this migration was written
+const WORDS_PER_MINUTE: i32 = 230;
+
+/// `domain::reading_time::word_count` as it was when this migration was
+/// written, so the migration stays reproducible.
+fn word_count(text: &str) -> i32 {
+    let words = text
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphabetic))
+        .count();
+    words.try_into().unwrap_or(i32::MAX)
+}
+
+fn reading_minutes(words: i32) -> i32 {
+    words / WORDS_PER_MINUTE + i32::from(words % WORDS_PER_MINUTE != 0)
+}
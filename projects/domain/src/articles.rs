@@ -1,25 +1,96 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::{
+    jobs::EnrichmentStatus, link_health::LinkHealth, reading_time::ReadingTime, tags::Tag,
+};
+
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct Article {
     pub uuid: Uuid,
     pub title: String,
     pub url: String,
+    /// The url the article was submitted with when it led to `url` only
+    /// through redirects or its canonical link, like a short link
+    #[serde(default)]
+    pub original_url: Option<String>,
+    pub description: Option<String>,
+    /// Where the article was found, e.g. the bluesky post or reddit thread
+    pub found_via: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Set while the article is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When the article was marked as read, `None` while it is unread
+    pub read_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// Hash of the stored favicon of the site, see [`crate::images`]
+    #[serde(default)]
+    pub favicon: Option<String>,
+    /// Hash of the stored `og:image` of the page
+    #[serde(default)]
+    pub preview_image: Option<String>,
+    /// Length of the readable content, `None` until the content was
+    /// extracted from the page
+    #[serde(default)]
+    pub reading_time: Option<ReadingTime>,
+    /// `None` once all background jobs of the article are done, it isn't
+    /// stored with the article
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrichment: Option<EnrichmentStatus>,
+    /// What the latest link check found wrong with the url, `None` while
+    /// the link is healthy or wasn't checked yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_health: Option<LinkHealth>,
 }
 
 impl Article {
-    pub fn new(uuid: Uuid, title: String, url: String) -> Self {
-        Self { uuid, title, url }
-    }
-
     pub fn from_parts(title: String, url: String) -> Self {
+        let now = Utc::now();
         Self {
             uuid: Uuid::new_v4(),
             title,
             url,
+            original_url: None,
+            description: None,
+            found_via: None,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+            read_at: None,
+            tags: vec![],
+            favicon: None,
+            preview_image: None,
+            reading_time: None,
+            enrichment: None,
+            link_health: None,
         }
     }
+
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+
+    pub fn with_tags(mut self, tags: Vec<Tag>) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+/// Restricts which articles are shown in the list, every field that is set
+/// has to match.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct ArticleFilter {
+    /// name of a tag the article has to be tagged with
+    pub tag: Option<String>,
+    /// only articles that haven't been read yet
+    pub unread_only: bool,
+    /// only articles that can be read within this many minutes, articles of
+    /// unknown length don't match
+    pub max_minutes: Option<u32>,
 }
 
 #[cfg(test)]
@@ -32,4 +103,10 @@ mod tests {
         assert!(!art.uuid.is_nil());
         assert!(!art.uuid.is_max());
     }
+
+    #[test]
+    fn article_from_parts_sets_equal_timestamps() {
+        let art = Article::from_parts(String::from("title"), String::from("url"));
+        assert_eq!(art.created_at, art.updated_at);
+    }
 }
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::tags::Tag;
+
+/// Subscription to an RSS or Atom feed, new entries of the feed are added
+/// to the collection when it is polled.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Feed {
+    pub uuid: Uuid,
+    pub url: String,
+    /// Title of the feed itself, known after it was polled the first time
+    pub title: Option<String>,
+    /// Tags every article ingested from this feed gets
+    pub tags: Vec<Tag>,
+    pub created_at: DateTime<Utc>,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    /// Why the last poll failed, `None` if it succeeded
+    pub last_error: Option<String>,
+}
+
+impl Feed {
+    pub fn new(url: String, tags: Vec<Tag>) -> Self {
+        Self {
+            uuid: Uuid::new_v4(),
+            url,
+            title: None,
+            tags,
+            created_at: Utc::now(),
+            last_polled_at: None,
+            last_error: None,
+        }
+    }
+
+    /// Feeds that were never polled or not since `interval` are due.
+    pub fn is_due(&self, now: DateTime<Utc>, interval: chrono::Duration) -> bool {
+        self.last_polled_at
+            .is_none_or(|polled_at| polled_at + interval <= now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::feeds::Feed;
+
+    #[test]
+    fn new_feed_is_due() {
+        let feed = Feed::new(String::from("https://example.com/feed"), vec![]);
+        assert!(feed.is_due(Utc::now(), Duration::minutes(30)));
+    }
+
+    #[test]
+    fn feed_is_due_after_interval() {
+        let now = Utc::now();
+        let mut feed = Feed::new(String::from("https://example.com/feed"), vec![]);
+        feed.last_polled_at = Some(now - Duration::minutes(10));
+        assert!(!feed.is_due(now, Duration::minutes(30)));
+        assert!(feed.is_due(now, Duration::minutes(10)));
+    }
+}
@@ -0,0 +1,39 @@
+//! Favicons and preview images of articles, stored on the server so pages
+//! never load images from other sites.
+
+/// A downscaled image, addressed by the sha256 of its data so every image
+/// is stored only once.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredImage {
+    /// Lowercase hex of the sha256 of `data`
+    pub hash: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Where the stored image is served.
+pub fn image_url(hash: &str) -> String {
+    format!("/images/{hash}")
+}
+
+/// Whether the text can be the hash of a stored image.
+pub fn is_image_hash(text: &str) -> bool {
+    text.len() == 64
+        && text
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::images::is_image_hash;
+
+    #[test]
+    fn only_sha256_hex_is_a_hash() {
+        let hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        assert!(is_image_hash(hash));
+        assert!(!is_image_hash(&hash.to_uppercase()));
+        assert!(!is_image_hash(&hash[1..]));
+        assert!(!is_image_hash("../../etc/passwd"));
+    }
+}
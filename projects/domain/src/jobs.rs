@@ -0,0 +1,182 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Enrichment of an article that is done in the background after the
+/// article was added, so adding articles never waits for other sites.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum JobKind {
+    /// Url of a short link or AMP page, the other jobs are queued once it
+    /// is resolved
+    Resolve,
+    /// Title, description and canonical url of the page
+    Metadata,
+    /// Favicon of the site and preview image of the page
+    Images,
+    /// Readable content of the page for the reader view
+    Content,
+    /// Self-contained copy of the page for the archive
+    Snapshot,
+}
+
+impl JobKind {
+    /// The jobs that enrich an article, each new article gets one of each.
+    /// Articles whose url needs resolving get them after [`JobKind::Resolve`].
+    pub const ENRICHMENT: [JobKind; 4] = [
+        JobKind::Metadata,
+        JobKind::Images,
+        JobKind::Content,
+        JobKind::Snapshot,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::Resolve => "resolve",
+            JobKind::Metadata => "metadata",
+            JobKind::Images => "images",
+            JobKind::Content => "content",
+            JobKind::Snapshot => "snapshot",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "resolve" => Some(JobKind::Resolve),
+            "metadata" => Some(JobKind::Metadata),
+            "images" => Some(JobKind::Images),
+            "content" => Some(JobKind::Content),
+            "snapshot" => Some(JobKind::Snapshot),
+            _ => None,
+        }
+    }
+}
+
+/// A queued job. Jobs that are done are removed from the queue, failed jobs
+/// are kept so the failure can be shown with the article.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Job {
+    pub uuid: Uuid,
+    pub article_uuid: Uuid,
+    pub kind: JobKind,
+    /// How often the job was started, including a running attempt
+    pub attempts: u32,
+    /// The job isn't started before this time
+    pub run_after: DateTime<Utc>,
+    pub last_error: Option<String>,
+    /// Set once the job failed [`MAX_ATTEMPTS`] times, it isn't retried
+    pub failed_at: Option<DateTime<Utc>>,
+}
+
+/// How often a job is started before it counts as failed
+pub const MAX_ATTEMPTS: u32 = 5;
+
+impl Job {
+    pub fn new(article_uuid: Uuid, kind: JobKind) -> Self {
+        Self {
+            uuid: Uuid::new_v4(),
+            article_uuid,
+            kind,
+            attempts: 0,
+            run_after: Utc::now(),
+            last_error: None,
+            failed_at: None,
+        }
+    }
+
+    /// Records the failed attempt and schedules the next one with an
+    /// exponential backoff, or gives up after [`MAX_ATTEMPTS`] attempts.
+    pub fn failed(&mut self, error: String, now: DateTime<Utc>) {
+        self.last_error = Some(error);
+        match self.attempts >= MAX_ATTEMPTS {
+            true => self.failed_at = Some(now),
+            false => self.run_after = now + retry_delay(self.attempts),
+        }
+    }
+}
+
+/// 1, 2, 4, 8, ... minutes after the first, second, third, ... attempt.
+pub fn retry_delay(attempts: u32) -> Duration {
+    Duration::minutes(1 << attempts.saturating_sub(1).min(10))
+}
+
+/// What is shown next to an article while its jobs aren't done.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum EnrichmentStatus {
+    Enriching,
+    /// why the last failed job failed
+    Failed(String),
+}
+
+impl EnrichmentStatus {
+    /// A single failed job is enough for the article to count as failed,
+    /// `None` if the article has no jobs left.
+    pub fn of<'a>(jobs: impl IntoIterator<Item = &'a Job>) -> Option<Self> {
+        let mut status = None;
+        for job in jobs {
+            if job.failed_at.is_some() {
+                let error = job.last_error.clone().unwrap_or_default();
+                return Some(EnrichmentStatus::Failed(error));
+            }
+            status = Some(EnrichmentStatus::Enriching);
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use crate::jobs::{EnrichmentStatus, Job, JobKind, MAX_ATTEMPTS, retry_delay};
+
+    #[test]
+    fn retry_delay_doubles() {
+        assert_eq!(Duration::minutes(1), retry_delay(1));
+        assert_eq!(Duration::minutes(2), retry_delay(2));
+        assert_eq!(Duration::minutes(8), retry_delay(4));
+        assert_eq!(Duration::minutes(1024), retry_delay(100));
+    }
+
+    #[test]
+    fn failed_job_is_retried_until_max_attempts() {
+        let now = Utc::now();
+        let mut job = Job::new(Uuid::new_v4(), JobKind::Metadata);
+        job.attempts = 2;
+        job.failed(String::from("timed out"), now);
+        assert_eq!(now + Duration::minutes(2), job.run_after);
+        assert_eq!(None, job.failed_at);
+
+        job.attempts = MAX_ATTEMPTS;
+        job.failed(String::from("timed out again"), now);
+        assert_eq!(Some(now), job.failed_at);
+        assert_eq!(Some(String::from("timed out again")), job.last_error);
+    }
+
+    #[test]
+    fn failed_job_decides_status() {
+        let article_uuid = Uuid::new_v4();
+        let pending = Job::new(article_uuid, JobKind::Metadata);
+        let mut failed = Job::new(article_uuid, JobKind::Metadata);
+        failed.attempts = MAX_ATTEMPTS;
+        failed.failed(String::from("404"), Utc::now());
+
+        assert_eq!(None, EnrichmentStatus::of(&[]));
+        assert_eq!(
+            Some(EnrichmentStatus::Enriching),
+            EnrichmentStatus::of(std::slice::from_ref(&pending))
+        );
+        assert_eq!(
+            Some(EnrichmentStatus::Failed(String::from("404"))),
+            EnrichmentStatus::of(&[pending, failed])
+        );
+    }
+
+    #[test]
+    fn job_kind_names_round_trip() {
+        for kind in JobKind::ENRICHMENT.into_iter().chain([JobKind::Resolve]) {
+            assert_eq!(Some(kind), JobKind::from_name(kind.as_str()));
+        }
+        assert_eq!(None, JobKind::from_name("unknown"));
+    }
+}
@@ -1 +1,14 @@
 pub mod articles;
+pub mod feeds;
+pub mod images;
+pub mod jobs;
+pub mod link_health;
+pub mod pagination;
+pub mod paste;
+pub mod reader;
+pub mod reading_time;
+pub mod snapshots;
+pub mod submission;
+pub mod tag_rules;
+pub mod tags;
+pub mod urls;
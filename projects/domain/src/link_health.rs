@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::urls::comparable_url;
+
+/// The outcome of the latest check of an article's link.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LinkCheck {
+    /// The url that was checked, the check is outdated once the url of the
+    /// article changed
+    pub url: String,
+    /// `None` if the site couldn't be reached at all
+    pub status: Option<u16>,
+    /// Where the url redirected to, `None` without redirects
+    pub final_url: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// What is wrong with the link of an article, it isn't stored with the
+/// article.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum LinkHealth {
+    /// The page responds with an error or the site can't be reached
+    Broken { status: Option<u16> },
+    /// The page redirects to another page
+    Moved { to: String },
+}
+
+impl LinkCheck {
+    /// What is wrong with `url` according to this check, `None` for healthy
+    /// links and checks of another url. Redirects to another spelling of the
+    /// same url, like from http to https or to the `www.` subdomain, aren't
+    /// moves.
+    pub fn health(&self, url: &str) -> Option<LinkHealth> {
+        if self.url != url {
+            return None;
+        }
+        match (self.status, &self.final_url) {
+            (None, _) => Some(LinkHealth::Broken { status: None }),
+            (Some(status), _) if status >= 400 => Some(LinkHealth::Broken {
+                status: Some(status),
+            }),
+            (Some(_), Some(final_url)) if !is_same_page(final_url, url) => {
+                Some(LinkHealth::Moved {
+                    to: final_url.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl LinkHealth {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LinkHealth::Broken { .. } => "broken",
+            LinkHealth::Moved { .. } => "moved",
+        }
+    }
+
+    /// Why the link got its label.
+    pub fn reason(&self) -> String {
+        match self {
+            LinkHealth::Broken {
+                status: Some(status),
+            } => format!("responds with status {status}"),
+            LinkHealth::Broken { status: None } => String::from("can't be reached"),
+            LinkHealth::Moved { to } => format!("redirects to {to}"),
+        }
+    }
+}
+
+fn is_same_page(a: &str, b: &str) -> bool {
+    fn without_scheme(url: &str) -> String {
+        let url = comparable_url(url);
+        let rest = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+        rest.strip_prefix("www.").unwrap_or(rest).to_string()
+    }
+    without_scheme(a) == without_scheme(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use crate::link_health::{LinkCheck, LinkHealth};
+
+    const URL: &str = "http://example.com/post";
+
+    fn check(status: Option<u16>, final_url: Option<&str>) -> LinkCheck {
+        LinkCheck {
+            url: URL.to_string(),
+            status,
+            final_url: final_url.map(String::from),
+            checked_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn error_statuses_and_unreachable_sites_are_broken() {
+        assert_eq!(
+            Some(LinkHealth::Broken { status: Some(404) }),
+            check(Some(404), None).health(URL)
+        );
+        assert_eq!(
+            Some(LinkHealth::Broken { status: None }),
+            check(None, None).health(URL)
+        );
+        assert_eq!(None, check(Some(200), None).health(URL));
+    }
+
+    #[test]
+    fn redirects_to_other_pages_are_moves() {
+        assert_eq!(
+            Some(LinkHealth::Moved {
+                to: String::from("https://example.org/")
+            }),
+            check(Some(200), Some("https://example.org/")).health(URL)
+        );
+    }
+
+    #[test]
+    fn redirects_to_the_same_page_are_healthy() {
+        for final_url in [
+            "https://example.com/post",
+            "http://example.com/post/",
+            "https://example.com/post?utm_source=feed",
+            "https://www.example.com/post",
+        ] {
+            assert_eq!(None, check(Some(200), Some(final_url)).health(URL));
+        }
+    }
+
+    #[test]
+    fn checks_of_another_url_are_outdated() {
+        assert_eq!(
+            None,
+            check(Some(404), None).health("https://example.com/new")
+        );
+    }
+}
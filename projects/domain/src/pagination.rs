@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::articles::Article;
+
+/// Position in the list of articles ordered by newest first, the next page
+/// starts with the article right after the one the cursor points to.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub uuid: Uuid,
+}
+
+impl Cursor {
+    pub fn after(article: &Article) -> Self {
+        Self {
+            created_at: article.created_at,
+            uuid: article.uuid,
+        }
+    }
+
+    /// Url safe form of the cursor, like `1700000000000000.<uuid>`, for
+    /// links to the next page.
+    pub fn to_token(&self) -> String {
+        format!("{}.{}", self.created_at.timestamp_micros(), self.uuid)
+    }
+
+    pub fn from_token(token: &str) -> Option<Self> {
+        let (micros, uuid) = token.split_once('.')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            uuid: uuid.parse().ok()?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+pub struct ArticlePage {
+    pub articles: Vec<Article>,
+    /// `None` if this is the last page
+    pub next: Option<Cursor>,
+}
+
+impl ArticlePage {
+    /// Builds a page from a query that fetched one more article than the page
+    /// size, the extra article only signals that there is a next page.
+    pub fn from_overfetched(mut articles: Vec<Article>, page_size: usize) -> Self {
+        let next = match articles.len() > page_size {
+            true => {
+                articles.truncate(page_size);
+                articles.last().map(Cursor::after)
+            }
+            false => None,
+        };
+
+        Self { articles, next }
+    }
+
+    /// A page that contains all articles, used for results that are not
+    /// paginated.
+    pub fn complete(articles: Vec<Article>) -> Self {
+        Self {
+            articles,
+            next: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::{
+        articles::Article,
+        pagination::{ArticlePage, Cursor},
+    };
+
+    #[test]
+    fn overfetched_page_points_to_last_kept_article() {
+        let articles = articles(4);
+        let page = ArticlePage::from_overfetched(articles.clone(), 3);
+        assert_eq!(3, page.articles.len());
+        assert_eq!(Some(Cursor::after(&articles[2])), page.next);
+    }
+
+    #[test]
+    fn page_without_extra_article_is_last() {
+        let page = ArticlePage::from_overfetched(articles(3), 3);
+        assert_eq!(3, page.articles.len());
+        assert_eq!(None, page.next);
+    }
+
+    #[test]
+    fn cursor_token_round_trips() {
+        // postgres stores timestamps with microsecond precision
+        let mut article = articles(1).remove(0);
+        article.created_at = DateTime::from_timestamp_micros(1700000000123456).unwrap();
+        let cursor = Cursor::after(&article);
+        assert_eq!(Some(cursor), Cursor::from_token(&cursor.to_token()));
+    }
+
+    #[test]
+    fn invalid_cursor_token_is_none() {
+        assert_eq!(None, Cursor::from_token("1700000000"));
+        assert_eq!(None, Cursor::from_token("yesterday.not-a-uuid"));
+    }
+
+    fn articles(count: usize) -> Vec<Article> {
+        (0..count)
+            .map(|i| Article::from_parts(format!("title {i}"), format!("url {i}")))
+            .collect()
+    }
+}
@@ -0,0 +1,547 @@
+use crate::urls::{UrlError, normalize_url};
+
+/// A link found in pasted text together with the title it was given.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PastedLink {
+    /// `None` if the text only contained the url
+    pub title: Option<String>,
+    pub url: String,
+}
+
+impl PastedLink {
+    fn new(title: Option<&str>, url: &str) -> Self {
+        let title = title
+            .map(str::trim)
+            .filter(|t| !t.is_empty() && !t.eq_ignore_ascii_case(url))
+            .map(String::from);
+        Self {
+            title,
+            url: url.into(),
+        }
+    }
+
+    /// The given title or one built by [`get_title_from_url`].
+    pub fn title_or_from_url(&self) -> String {
+        self.title
+            .clone()
+            .unwrap_or_else(|| get_title_from_url(self.url.clone()))
+    }
+}
+
+/// Something that was meant to be a link but isn't a valid url, `text` is
+/// the part of the line it was found in.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidLink {
+    pub text: String,
+    pub error: UrlError,
+}
+
+pub type ParsedLink = Result<PastedLink, InvalidLink>;
+
+/// Every valid link found in the pasted text, see [`parse_line`].
+pub fn parse_pasted(text: &str) -> Vec<PastedLink> {
+    text.lines()
+        .flat_map(parse_line)
+        .filter_map(Result::ok)
+        .collect()
+}
+
+/// Finds the links in a single line of pasted text. Understood are:
+/// - `title | url` lines, if the part after the last pipe looks like a url
+/// - a line only containing a url (or something that should be one)
+/// - Markdown links like `[title](url)` and `<url>`
+/// - urls somewhere in free text like a Bluesky or Reddit post
+///
+/// Links only get a title if the line gives them one.
+pub fn parse_line(line: &str) -> Vec<ParsedLink> {
+    let line = line.trim();
+    if line.is_empty() {
+        return vec![];
+    }
+
+    if let Some((title, url)) = line.rsplit_once('|') {
+        let url = url.trim();
+        if is_single_token(url) && looks_like_url(url) && !title.contains("](") {
+            let title = title.trim().trim_start_matches(['-', '*', '+']);
+            return vec![link(Some(title), url, line)];
+        }
+    }
+
+    let links = find_links(line);
+    if links.is_empty() && is_single_token(line) && looks_like_url(line) {
+        return vec![link(None, line, line)];
+    }
+    links
+}
+
+fn link(title: Option<&str>, url: &str, text: &str) -> ParsedLink {
+    match normalize_url(url) {
+        Ok(_) => Ok(PastedLink::new(title, url)),
+        Err(error) => Err(InvalidLink {
+            text: text.into(),
+            error,
+        }),
+    }
+}
+
+fn is_single_token(text: &str) -> bool {
+    !text.is_empty() && !text.contains(char::is_whitespace)
+}
+
+/// Bare words like "nice!" are just text, but a lone `example.com/article`
+/// was most likely meant to be a link and should be reported as invalid.
+fn looks_like_url(text: &str) -> bool {
+    text.contains("://") || text.trim_end_matches('.').contains('.')
+}
+
+/// Markdown links and free urls of the line in the order they appear in.
+fn find_links(line: &str) -> Vec<ParsedLink> {
+    let markdown = markdown_links(line);
+    let mut links = markdown
+        .iter()
+        .filter(|m| !m.image)
+        .map(|m| (m.start, link(Some(m.title), m.url, &line[m.start..m.end])))
+        .collect::<Vec<_>>();
+
+    let lowercase = line.to_ascii_lowercase();
+    let mut position = 0;
+    while let Some(start) = next_url_start(&lowercase, position) {
+        let end = url_end(line, start);
+        position = end;
+        if markdown.iter().any(|m| (m.start..m.end).contains(&start)) {
+            continue;
+        }
+        let url = &line[start..end];
+        links.push((start, link(None, url, url)));
+    }
+
+    links.sort_by_key(|(start, _)| *start);
+    links.into_iter().map(|(_, link)| link).collect()
+}
+
+fn next_url_start(lowercase: &str, from: usize) -> Option<usize> {
+    ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| lowercase[from..].find(scheme))
+        .min()
+        .map(|index| from + index)
+}
+
+/// A url ends at whitespace or a character that can't be part of one.
+/// Trailing punctuation and closing brackets without an opening one are
+/// part of the surrounding sentence.
+fn url_end(line: &str, start: usize) -> usize {
+    let rest = &line[start..];
+    let mut url = rest
+        .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '`' | '|'))
+        .map(|end| &rest[..end])
+        .unwrap_or(rest);
+
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '*', '_']);
+        let trimmed = match trimmed.chars().last() {
+            Some(')') if !is_balanced(trimmed, '(', ')') => &trimmed[..trimmed.len() - 1],
+            Some(']') if !is_balanced(trimmed, '[', ']') => &trimmed[..trimmed.len() - 1],
+            _ => trimmed,
+        };
+        if trimmed.len() == url.len() {
+            break;
+        }
+        url = trimmed;
+    }
+    start + url.len()
+}
+
+fn is_balanced(text: &str, open: char, close: char) -> bool {
+    text.matches(open).count() >= text.matches(close).count()
+}
+
+struct MarkdownLink<'a> {
+    start: usize,
+    end: usize,
+    title: &'a str,
+    url: &'a str,
+    /// images like `![alt](src)` are part of the text, not links to collect
+    image: bool,
+}
+
+/// Every `[title](url)` in the line. An optional link title like
+/// `[title](url "hover text")` is ignored.
+fn markdown_links(line: &str) -> Vec<MarkdownLink<'_>> {
+    let mut links = vec![];
+    let mut position = 0;
+
+    while let Some(offset) = line[position..].find("](") {
+        let middle = position + offset;
+        let Some(start) = matching_open(line, middle) else {
+            position = middle + 2;
+            continue;
+        };
+        let Some(end) = matching_close(line, middle + 1) else {
+            break;
+        };
+
+        let title = &line[start + 1..middle];
+        let target = line[middle + 2..end].trim();
+        let url = target
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches('<')
+            .trim_end_matches('>');
+
+        if !url.is_empty() {
+            links.push(MarkdownLink {
+                start,
+                end: end + 1,
+                title,
+                url,
+                image: line[..start].ends_with('!'),
+            });
+        }
+        position = end + 1;
+    }
+    links
+}
+
+/// Index of the `[` belonging to the `]` at `close`.
+fn matching_open(line: &str, close: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in line[..close].char_indices().rev() {
+        match c {
+            ']' => depth += 1,
+            '[' if depth == 0 => return Some(index),
+            '[' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Index of the `)` belonging to the `(` at `open`.
+fn matching_close(line: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in line[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return Some(open + index),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Builds a title like `github.com - modern-latex` out of the host and the
+/// last path segment of the url.
+pub fn get_title_from_url(mut url: String) -> String {
+    let url = if url.to_ascii_lowercase().starts_with("https://") {
+        let _ = url.drain(0..8);
+        url
+    } else if url.to_ascii_lowercase().starts_with("http://") {
+        let _ = url.drain(0..7);
+        url
+    } else {
+        url
+    };
+    let mut parts = url.split('/');
+    let first = parts.next().unwrap().to_string();
+    let last = parts.rev().find(|p| !p.is_empty() && !p.eq(&first));
+
+    match last {
+        Some(last) => format!("{first} - {last}"),
+        None => first,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        paste::{InvalidLink, PastedLink, get_title_from_url, parse_line, parse_pasted},
+        urls::UrlError,
+    };
+
+    fn pasted(title: &str, url: &str) -> PastedLink {
+        PastedLink {
+            title: Some(title.into()),
+            url: url.into(),
+        }
+    }
+
+    fn untitled(url: &str) -> PastedLink {
+        PastedLink {
+            title: None,
+            url: url.into(),
+        }
+    }
+
+    #[test]
+    fn get_title_from_url_https_ending_slash() {
+        let url = "https://www.nasa.gov/centers-and-facilities/stennis/stennis-first-open-source-software/".into();
+        let res = get_title_from_url(url);
+        assert_eq!(
+            String::from("www.nasa.gov - stennis-first-open-source-software"),
+            res
+        );
+    }
+
+    #[test]
+    fn get_title_from_url_https() {
+        let url = "https://github.com/mrkline/modern-latex".into();
+        let res = get_title_from_url(url);
+        assert_eq!(String::from("github.com - modern-latex"), res);
+    }
+
+    #[test]
+    fn get_title_from_url_http() {
+        let url = "http://github.com/mrkline/modern-latex".into();
+        let res = get_title_from_url(url);
+        assert_eq!(String::from("github.com - modern-latex"), res);
+    }
+
+    #[test]
+    fn parse_line_bare_url() {
+        assert_eq!(
+            vec![Ok(untitled("https://github.com/mrkline/modern-latex"))],
+            parse_line("  https://github.com/mrkline/modern-latex ")
+        );
+    }
+
+    #[test]
+    fn parse_line_blank_has_no_links() {
+        assert!(parse_line("   ").is_empty());
+    }
+
+    #[test]
+    fn parse_line_text_without_links() {
+        assert!(parse_line("what a great week for reading").is_empty());
+        assert!(parse_line("nice!").is_empty());
+    }
+
+    #[test]
+    fn parse_line_title_pipe_url() {
+        assert_eq!(
+            vec![Ok(pasted(
+                "Modern LaTeX",
+                "https://github.com/mrkline/modern-latex"
+            ))],
+            parse_line("Modern LaTeX | https://github.com/mrkline/modern-latex")
+        );
+    }
+
+    #[test]
+    fn parse_line_title_with_pipes() {
+        assert_eq!(
+            vec![Ok(pasted("Rust | Blog", "https://blog.rust-lang.org"))],
+            parse_line("Rust | Blog | https://blog.rust-lang.org")
+        );
+    }
+
+    #[test]
+    fn parse_line_title_pipe_invalid_url() {
+        assert_eq!(
+            vec![Err(InvalidLink {
+                text: String::from("Modern LaTeX | github.com/mrkline/modern-latex"),
+                error: UrlError::Parse(url::ParseError::RelativeUrlWithoutBase),
+            })],
+            parse_line("Modern LaTeX | github.com/mrkline/modern-latex")
+        );
+    }
+
+    #[test]
+    fn parse_line_note_after_pipe_is_text() {
+        assert_eq!(
+            vec![Ok(untitled("https://a.com"))],
+            parse_line("see https://a.com | note")
+        );
+        assert_eq!(
+            vec![Ok(untitled("https://a.com"))],
+            parse_line("see https://a.com | my note")
+        );
+    }
+
+    #[test]
+    fn parse_line_url_without_scheme_is_invalid() {
+        let links = parse_line("example.com/article");
+        assert_eq!(1, links.len());
+        assert_eq!("example.com/article", links[0].as_ref().unwrap_err().text);
+    }
+
+    #[test]
+    fn parse_line_unsupported_scheme_is_invalid() {
+        assert_eq!(
+            vec![Err(InvalidLink {
+                text: String::from("ftp://example.com/file.txt"),
+                error: UrlError::UnsupportedScheme(String::from("ftp")),
+            })],
+            parse_line("ftp://example.com/file.txt")
+        );
+    }
+
+    #[test]
+    fn parse_line_markdown_link() {
+        assert_eq!(
+            vec![Ok(pasted(
+                "The Rust Book",
+                "https://doc.rust-lang.org/book/"
+            ))],
+            parse_line("- [The Rust Book](https://doc.rust-lang.org/book/)")
+        );
+    }
+
+    #[test]
+    fn parse_line_markdown_link_with_hover_title() {
+        assert_eq!(
+            vec![Ok(pasted("Book", "https://doc.rust-lang.org/book/"))],
+            parse_line("[Book](https://doc.rust-lang.org/book/ \"The Rust Book\")")
+        );
+    }
+
+    #[test]
+    fn parse_line_markdown_link_with_parentheses_in_url() {
+        assert_eq!(
+            vec![Ok(pasted(
+                "Rust",
+                "https://en.wikipedia.org/wiki/Rust_(programming_language)"
+            ))],
+            parse_line("[Rust](https://en.wikipedia.org/wiki/Rust_(programming_language))")
+        );
+    }
+
+    #[test]
+    fn parse_line_markdown_link_with_brackets_in_title() {
+        assert_eq!(
+            vec![Ok(pasted(
+                "[RFC] async closures",
+                "https://example.com/rfc"
+            ))],
+            parse_line("[[RFC] async closures](https://example.com/rfc)")
+        );
+    }
+
+    #[test]
+    fn parse_line_markdown_link_url_as_title() {
+        assert_eq!(
+            vec![Ok(untitled("https://github.com/mrkline/modern-latex"))],
+            parse_line(
+                "[https://github.com/mrkline/modern-latex](https://github.com/mrkline/modern-latex)"
+            )
+        );
+    }
+
+    #[test]
+    fn parse_line_markdown_relative_link_is_invalid() {
+        let links = parse_line("see [the docs](/docs/intro)");
+        assert_eq!(1, links.len());
+        assert_eq!(
+            "[the docs](/docs/intro)",
+            links[0].as_ref().unwrap_err().text
+        );
+    }
+
+    #[test]
+    fn parse_line_markdown_image_is_skipped() {
+        assert!(parse_line("![a cat](https://example.com/cat.png)").is_empty());
+    }
+
+    #[test]
+    fn parse_line_markdown_autolink() {
+        assert_eq!(
+            vec![Ok(untitled("https://example.com/post"))],
+            parse_line("<https://example.com/post>")
+        );
+    }
+
+    #[test]
+    fn parse_line_multiple_markdown_links_in_order() {
+        assert_eq!(
+            vec![
+                Ok(pasted("one", "https://example.com/1")),
+                Ok(pasted("two", "https://example.com/2")),
+            ],
+            parse_line("[one](https://example.com/1) and [two](https://example.com/2)")
+        );
+    }
+
+    #[test]
+    fn parse_line_free_text_urls() {
+        assert_eq!(
+            vec![
+                Ok(untitled("https://example.com/first")),
+                Ok(untitled("http://example.org/second")),
+            ],
+            parse_line("Read this https://example.com/first, then (http://example.org/second).")
+        );
+    }
+
+    #[test]
+    fn parse_line_free_text_mixed_with_markdown() {
+        assert_eq!(
+            vec![
+                Ok(pasted("docs", "https://docs.rs")),
+                Ok(untitled("https://crates.io")),
+            ],
+            parse_line("[docs](https://docs.rs) or https://crates.io!")
+        );
+    }
+
+    #[test]
+    fn parse_line_keeps_balanced_parentheses_in_free_url() {
+        assert_eq!(
+            vec![Ok(untitled(
+                "https://en.wikipedia.org/wiki/Rust_(programming_language)"
+            ))],
+            parse_line("see https://en.wikipedia.org/wiki/Rust_(programming_language).")
+        );
+    }
+
+    #[test]
+    fn parse_line_uppercase_scheme() {
+        assert_eq!(
+            vec![Ok(untitled("HTTPS://EXAMPLE.COM/Post"))],
+            parse_line("look: HTTPS://EXAMPLE.COM/Post")
+        );
+    }
+
+    #[test]
+    fn parse_line_keeps_query_parameters() {
+        assert_eq!(
+            vec![Ok(untitled("https://news.ycombinator.com/item?id=1"))],
+            parse_line("via https://news.ycombinator.com/item?id=1")
+        );
+    }
+
+    #[test]
+    fn parse_pasted_reddit_post() {
+        let post = "\
+Some great reads from this week:
+
+* [Rust 2024](https://blog.rust-lang.org/2025/02/20/Rust-1.85.0.html)
+* Sea ORM | https://www.sea-ql.org/SeaORM/
+* and also https://leptos.dev/ (thanks u/someone)
+
+![screenshot](https://i.redd.it/abc.png)
+Cheers!";
+        assert_eq!(
+            vec![
+                pasted(
+                    "Rust 2024",
+                    "https://blog.rust-lang.org/2025/02/20/Rust-1.85.0.html"
+                ),
+                pasted("Sea ORM", "https://www.sea-ql.org/SeaORM/"),
+                untitled("https://leptos.dev/"),
+            ],
+            parse_pasted(post)
+        );
+    }
+
+    #[test]
+    fn parse_pasted_bluesky_post() {
+        let post =
+            "Finally wrote up how we run Postgres: https://example.com/postgres-ops\n\n#databases";
+        assert_eq!(
+            vec![untitled("https://example.com/postgres-ops")],
+            parse_pasted(post)
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+/// The readable content of an article's page, shown in the reader view.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ArticleContent {
+    /// Only contains allowlisted tags and attributes, so it can be shown as
+    /// it is
+    pub html: String,
+    /// One paragraph per line, separated by empty lines
+    pub text: String,
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// Average reading speed of adults for non-fiction
+pub const WORDS_PER_MINUTE: u32 = 230;
+
+/// How long the content of an article is.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReadingTime {
+    pub words: u32,
+    /// Rounded up, at least one minute for any text
+    pub minutes: u32,
+}
+
+impl ReadingTime {
+    /// The reading time of plain text like [`crate::reader::ArticleContent::text`].
+    pub fn of(text: &str) -> Self {
+        let words = word_count(text);
+        Self {
+            words,
+            minutes: reading_minutes(words),
+        }
+    }
+}
+
+/// Words are separated by whitespace, punctuation and numbers on their own
+/// like `-` or `1.` don't count.
+pub fn word_count(text: &str) -> u32 {
+    let words = text
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphabetic))
+        .count();
+    u32::try_from(words).unwrap_or(u32::MAX)
+}
+
+pub fn reading_minutes(words: u32) -> u32 {
+    words.div_ceil(WORDS_PER_MINUTE)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reading_time::{ReadingTime, WORDS_PER_MINUTE, reading_minutes, word_count};
+
+    #[test]
+    fn words_are_separated_by_whitespace() {
+        assert_eq!(5, word_count("How reader\nmodes\n\n  work, roughly."));
+        assert_eq!(0, word_count("   \n\n "));
+    }
+
+    #[test]
+    fn punctuation_and_numbers_are_not_words() {
+        assert_eq!(3, word_count("1. Step one - done 42 —"));
+    }
+
+    #[test]
+    fn words_with_letters_of_any_script_count() {
+        assert_eq!(3, word_count("Straße überall Москва"));
+    }
+
+    #[test]
+    fn minutes_are_rounded_up() {
+        assert_eq!(0, reading_minutes(0));
+        assert_eq!(1, reading_minutes(1));
+        assert_eq!(1, reading_minutes(WORDS_PER_MINUTE));
+        assert_eq!(2, reading_minutes(WORDS_PER_MINUTE + 1));
+    }
+
+    #[test]
+    fn reading_time_of_a_text() {
+        let text = "word ".repeat(2300);
+        assert_eq!(
+            ReadingTime {
+                words: 2300,
+                minutes: 10
+            },
+            ReadingTime::of(&text)
+        );
+    }
+}
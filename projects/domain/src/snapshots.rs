@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A self-contained copy of an article's page, so the article can still be
+/// read when the page is gone.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    /// The page with its stylesheets and images inlined and its scripts
+    /// removed
+    pub html: String,
+    /// Lowercase hex of the sha256 of `html`
+    pub hash: String,
+    pub taken_at: DateTime<Utc>,
+}
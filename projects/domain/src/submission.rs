@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    articles::Article,
+    paste::{InvalidLink, PastedLink, parse_line},
+};
+
+/// What happened to a single line of a bulk submission
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum LineOutcome {
+    Inserted,
+    /// The url is already part of the collection or appeared earlier in
+    /// the same submission
+    Duplicate,
+    /// The link is not a valid url, contains the reason
+    InvalidUrl(String),
+    /// The line is only text without any links
+    NoUrl,
+    Blank,
+}
+
+impl LineOutcome {
+    /// Lines the user has to fix before they can be added
+    pub fn is_failure(&self) -> bool {
+        matches!(self, LineOutcome::InvalidUrl(_))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LineReport {
+    /// 1-based line number in the submitted text
+    pub number: usize,
+    /// The link found in the line, or the line itself if it has none. A
+    /// line containing multiple links gets one report for each of them.
+    pub line: String,
+    pub outcome: LineOutcome,
+}
+
+/// Entry of a submission before it is inserted: the line number, the text
+/// to report it as and either the article to insert or why there is none.
+pub type SubmissionEntry = (usize, String, Result<Article, LineOutcome>);
+
+/// Like a [`SubmissionEntry`] but with the link the article is created from.
+pub type PastedEntry = (usize, String, Result<PastedLink, LineOutcome>);
+
+/// Every link of the pasted text together with the number of its line.
+/// Lines without any link get a single entry saying why.
+pub fn pasted_entries(text: &str) -> Vec<PastedEntry> {
+    let mut entries = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let links = parse_line(line);
+        if links.is_empty() {
+            let outcome = match line.trim().is_empty() {
+                true => LineOutcome::Blank,
+                false => LineOutcome::NoUrl,
+            };
+            entries.push((number, line.trim().to_string(), Err(outcome)));
+        }
+        for link in links {
+            entries.push(match link {
+                Ok(link) => (number, link.url.clone(), Ok(link)),
+                Err(InvalidLink { text, error }) => (
+                    number,
+                    text,
+                    Err(LineOutcome::InvalidUrl(error.to_string())),
+                ),
+            });
+        }
+    }
+    entries
+}
+
+/// Report of a bulk submission with one entry for every submitted line
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+pub struct SubmissionReport {
+    pub lines: Vec<LineReport>,
+}
+
+impl SubmissionReport {
+    pub fn push(&mut self, number: usize, line: &str, outcome: LineOutcome) {
+        self.lines.push(LineReport {
+            number,
+            line: line.into(),
+            outcome,
+        });
+    }
+
+    pub fn count(&self, outcome: fn(&LineOutcome) -> bool) -> usize {
+        self.lines.iter().filter(|l| outcome(&l.outcome)).count()
+    }
+
+    /// The failed lines joined back together, so they can be put back into
+    /// the input, fixed and submitted again.
+    pub fn failed_lines(&self) -> String {
+        self.lines
+            .iter()
+            .filter(|l| l.outcome.is_failure())
+            .map(|l| l.line.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        paste::PastedLink,
+        submission::{LineOutcome, SubmissionReport, pasted_entries},
+    };
+
+    #[test]
+    fn pasted_entries_numbers_every_link() {
+        let entries = pasted_entries(
+            "Rust | https://rust-lang.org\n\nno link here\nsee https://a.com and https://b.com\nDocs | ftp://example.com/file",
+        );
+        let link = |title: Option<&str>, url: &str| PastedLink {
+            title: title.map(String::from),
+            url: url.into(),
+        };
+
+        assert_eq!(
+            vec![
+                (
+                    1,
+                    String::from("https://rust-lang.org"),
+                    Ok(link(Some("Rust"), "https://rust-lang.org"))
+                ),
+                (2, String::new(), Err(LineOutcome::Blank)),
+                (3, String::from("no link here"), Err(LineOutcome::NoUrl)),
+                (
+                    4,
+                    String::from("https://a.com"),
+                    Ok(link(None, "https://a.com"))
+                ),
+                (
+                    4,
+                    String::from("https://b.com"),
+                    Ok(link(None, "https://b.com"))
+                ),
+            ],
+            entries[..5]
+        );
+        assert!(matches!(
+            entries[5],
+            (5, _, Err(LineOutcome::InvalidUrl(_)))
+        ));
+    }
+
+    #[test]
+    fn count_matches_outcomes() {
+        let mut report = SubmissionReport::default();
+        report.push(1, "https://example.com/a", LineOutcome::Inserted);
+        report.push(2, "https://example.com/b", LineOutcome::Inserted);
+        report.push(3, "https://example.com/a", LineOutcome::Duplicate);
+        report.push(4, "", LineOutcome::Blank);
+        assert_eq!(2, report.count(|o| *o == LineOutcome::Inserted));
+        assert_eq!(1, report.count(|o| *o == LineOutcome::Duplicate));
+        assert_eq!(0, report.count(LineOutcome::is_failure));
+    }
+
+    #[test]
+    fn failed_lines_only_contains_invalid_urls() {
+        let mut report = SubmissionReport::default();
+        report.push(1, "https://example.com", LineOutcome::Inserted);
+        report.push(2, "nope", LineOutcome::InvalidUrl(String::from("err")));
+        report.push(3, "https://example.com", LineOutcome::Duplicate);
+        report.push(4, "", LineOutcome::Blank);
+        report.push(5, "also nope", LineOutcome::InvalidUrl(String::from("err")));
+        assert_eq!("nope\nalso nope", report.failed_lines());
+        assert_eq!(2, report.count(LineOutcome::is_failure));
+    }
+}
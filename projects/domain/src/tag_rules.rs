@@ -0,0 +1,374 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{articles::Article, tags::Tag};
+
+/// What a rule matches its pattern against.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum RuleKind {
+    /// The host of the url including its subdomains, `github.com` also
+    /// matches `gist.github.com`
+    Host,
+    /// The path of the url, `*` matches within a path segment and `**`
+    /// across segments
+    PathGlob,
+    /// A case insensitive regex searched in the title
+    TitleRegex,
+}
+
+impl RuleKind {
+    pub const ALL: [RuleKind; 3] = [RuleKind::Host, RuleKind::PathGlob, RuleKind::TitleRegex];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleKind::Host => "host",
+            RuleKind::PathGlob => "path",
+            RuleKind::TitleRegex => "title",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "host" => Some(RuleKind::Host),
+            "path" => Some(RuleKind::PathGlob),
+            "title" => Some(RuleKind::TitleRegex),
+            _ => None,
+        }
+    }
+}
+
+/// Tags every article matching the pattern with the tags of the rule.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TagRule {
+    pub uuid: Uuid,
+    pub kind: RuleKind,
+    pub pattern: String,
+    pub tags: Vec<Tag>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    EmptyPattern,
+    NoTags,
+    InvalidRegex(String),
+}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleError::EmptyPattern => write!(f, "the pattern can't be empty"),
+            RuleError::NoTags => write!(f, "a rule needs at least one tag"),
+            RuleError::InvalidRegex(err) => write!(f, "invalid regex: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+impl TagRule {
+    /// Creates a rule with a normalized pattern. Hosts are lowercased
+    /// without a leading `www.` and can also be given as an url, paths
+    /// always start with a `/`.
+    pub fn new(kind: RuleKind, pattern: &str, tags: Vec<Tag>) -> Result<Self, RuleError> {
+        let pattern = pattern.trim();
+        let pattern = match kind {
+            RuleKind::Host => {
+                let host = Url::parse(pattern)
+                    .ok()
+                    .and_then(|url| url.host_str().map(String::from))
+                    .unwrap_or_else(|| pattern.to_string())
+                    .to_lowercase();
+                host.trim_start_matches("www.").to_string()
+            }
+            RuleKind::PathGlob if !pattern.is_empty() && !pattern.starts_with('/') => {
+                format!("/{pattern}")
+            }
+            RuleKind::PathGlob => pattern.to_string(),
+            RuleKind::TitleRegex => {
+                title_regex(pattern).map_err(|err| RuleError::InvalidRegex(err.to_string()))?;
+                pattern.to_string()
+            }
+        };
+
+        if pattern.is_empty() {
+            return Err(RuleError::EmptyPattern);
+        }
+        if tags.is_empty() {
+            return Err(RuleError::NoTags);
+        }
+
+        Ok(Self {
+            uuid: Uuid::new_v4(),
+            kind,
+            pattern,
+            tags,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// The rules prepared for matching many articles, regexes are only compiled
+/// once.
+pub struct RuleSet {
+    rules: Vec<(Matcher, Vec<Tag>)>,
+}
+
+enum Matcher {
+    Host(String),
+    PathGlob(String),
+    TitleRegex(Regex),
+}
+
+impl RuleSet {
+    /// Rules with an invalid regex never match.
+    pub fn new(rules: Vec<TagRule>) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter_map(|rule| {
+                let matcher = match rule.kind {
+                    RuleKind::Host => Matcher::Host(rule.pattern),
+                    RuleKind::PathGlob => Matcher::PathGlob(rule.pattern),
+                    RuleKind::TitleRegex => Matcher::TitleRegex(title_regex(&rule.pattern).ok()?),
+                };
+                Some((matcher, rule.tags))
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Adds the tags of all matching rules the article doesn't have yet,
+    /// returns whether any tag was added.
+    pub fn apply(&self, article: &mut Article) -> bool {
+        let url = Url::parse(&article.url).ok();
+        let mut added = false;
+
+        for (matcher, tags) in &self.rules {
+            let matches = match matcher {
+                Matcher::Host(host) => url
+                    .as_ref()
+                    .and_then(Url::host_str)
+                    .is_some_and(|url_host| is_host_or_subdomain(url_host, host)),
+                Matcher::PathGlob(glob) => url
+                    .as_ref()
+                    .is_some_and(|url| glob_matches(glob, url.path())),
+                Matcher::TitleRegex(regex) => regex.is_match(&article.title),
+            };
+            if !matches {
+                continue;
+            }
+
+            for tag in tags {
+                if !article.tags.iter().any(|t| t.name == tag.name) {
+                    article.tags.push(tag.clone());
+                    added = true;
+                }
+            }
+        }
+        added
+    }
+
+    /// Applies the rules to the enriched article if its title or url differ
+    /// from the article before, e.g. once the title of the page replaced
+    /// the one built from the url. Unchanged articles keep their tags, so
+    /// tags that were removed by hand aren't added again.
+    pub fn apply_to_changed(&self, before: &Article, after: &mut Article) -> bool {
+        if before.title == after.title && before.url == after.url {
+            return false;
+        }
+        self.apply(after)
+    }
+}
+
+fn title_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(1024 * 1024)
+        .build()
+}
+
+fn is_host_or_subdomain(url_host: &str, host: &str) -> bool {
+    let url_host = url_host.to_lowercase();
+    url_host == host
+        || url_host
+            .strip_suffix(host)
+            .is_some_and(|subdomain| subdomain.ends_with('.'))
+}
+
+/// Whether the whole path matches the glob, `*` matches anything but `/`,
+/// `**` matches anything and `?` a single character other than `/`.
+fn glob_matches(glob: &str, path: &str) -> bool {
+    let glob = glob.as_bytes();
+    let path = path.as_bytes();
+    // matches[j] says whether the glob so far matches the first j bytes
+    let mut matches = vec![false; path.len() + 1];
+    matches[0] = true;
+
+    let mut i = 0;
+    while i < glob.len() {
+        let mut next = vec![false; path.len() + 1];
+        match glob[i] {
+            b'*' if glob.get(i + 1) == Some(&b'*') => {
+                i += 1;
+                let mut any = false;
+                for j in 0..=path.len() {
+                    any |= matches[j];
+                    next[j] = any;
+                }
+            }
+            b'*' => {
+                for j in 0..=path.len() {
+                    next[j] = matches[j] || (j > 0 && next[j - 1] && path[j - 1] != b'/');
+                }
+            }
+            b'?' => {
+                for j in 1..=path.len() {
+                    next[j] = matches[j - 1] && path[j - 1] != b'/';
+                }
+            }
+            literal => {
+                for j in 1..=path.len() {
+                    next[j] = matches[j - 1] && path[j - 1] == literal;
+                }
+            }
+        }
+        matches = next;
+        i += 1;
+    }
+    matches[path.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        articles::Article,
+        paste::get_title_from_url,
+        tag_rules::{RuleError, RuleKind, RuleSet, TagRule, glob_matches},
+        tags::parse_tag_list,
+    };
+
+    fn rule(kind: RuleKind, pattern: &str, tags: &str) -> TagRule {
+        TagRule::new(kind, pattern, parse_tag_list(tags)).unwrap()
+    }
+
+    fn tag_names(article: &Article) -> Vec<&str> {
+        article.tags.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn hosts_match_with_their_subdomains() {
+        let rules = RuleSet::new(vec![rule(RuleKind::Host, "github.com", "code")]);
+
+        for url in [
+            "https://github.com/rust-lang/rust",
+            "https://www.github.com/rust-lang/rust",
+            "https://gist.GitHub.com/abc",
+        ] {
+            let mut article = Article::from_parts(String::from("Title"), url.to_string());
+            assert!(rules.apply(&mut article), "{url}");
+            assert_eq!(vec!["code"], tag_names(&article));
+        }
+
+        let mut other = Article::from_parts(
+            String::from("Title"),
+            String::from("https://notgithub.com/rust"),
+        );
+        assert!(!rules.apply(&mut other));
+    }
+
+    #[test]
+    fn host_patterns_are_normalized() {
+        assert_eq!(
+            "arxiv.org",
+            rule(RuleKind::Host, " https://www.arXiv.org/abs/1 ", "paper").pattern
+        );
+        assert_eq!("/watch", rule(RuleKind::PathGlob, "watch", "video").pattern);
+    }
+
+    #[test]
+    fn path_globs_match_the_whole_path() {
+        assert!(glob_matches("/watch", "/watch"));
+        assert!(glob_matches("/*/issues/*", "/rust-lang/issues/12"));
+        assert!(!glob_matches("/*/issues/*", "/rust-lang/rust/issues/12"));
+        assert!(glob_matches("/**/issues/*", "/rust-lang/rust/issues/12"));
+        assert!(glob_matches("/abs/????.*", "/abs/2401.12345"));
+        assert!(!glob_matches("/watch", "/watch/later"));
+        assert!(glob_matches("/**", "/"));
+    }
+
+    #[test]
+    fn title_regexes_ignore_case() {
+        let rules = RuleSet::new(vec![rule(RuleKind::TitleRegex, r"\brust\b", "rust")]);
+        let mut article = Article::from_parts(
+            String::from("Why Rust is fast"),
+            String::from("https://example.com/post"),
+        );
+        assert!(rules.apply(&mut article));
+        assert_eq!(vec!["rust"], tag_names(&article));
+    }
+
+    #[test]
+    fn title_regexes_match_the_title_of_the_enriched_article() {
+        let rules = RuleSet::new(vec![rule(RuleKind::TitleRegex, r"\brust\b", "rust")]);
+        let url = String::from("https://example.com/posts/1234");
+        let mut submitted = Article::from_parts(get_title_from_url(url.clone()), url);
+        assert!(!rules.apply(&mut submitted));
+
+        let mut enriched = submitted.clone();
+        enriched.title = String::from("Why Rust is fast");
+        assert!(rules.apply_to_changed(&submitted, &mut enriched));
+        assert_eq!(vec!["rust"], tag_names(&enriched));
+    }
+
+    #[test]
+    fn unchanged_articles_keep_their_tags() {
+        let rules = RuleSet::new(vec![rule(RuleKind::TitleRegex, "rust", "rust")]);
+        let before = Article::from_parts(
+            String::from("Why Rust is fast"),
+            String::from("https://example.com/post"),
+        );
+        let mut after = before.clone();
+        after.description = Some(String::from("It compiles to machine code"));
+
+        assert!(!rules.apply_to_changed(&before, &mut after));
+        assert!(after.tags.is_empty());
+    }
+
+    #[test]
+    fn existing_tags_are_kept_and_not_duplicated() {
+        let rules = RuleSet::new(vec![
+            rule(RuleKind::Host, "youtube.com", "video, talks"),
+            rule(RuleKind::PathGlob, "/watch", "video"),
+        ]);
+        let mut article = Article::from_parts(
+            String::from("A talk"),
+            String::from("https://www.youtube.com/watch?v=abc"),
+        )
+        .with_tags(parse_tag_list("talks, later"));
+
+        assert!(rules.apply(&mut article));
+        assert_eq!(vec!["talks", "later", "video"], tag_names(&article));
+        assert!(!rules.apply(&mut article));
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert_eq!(
+            Err(RuleError::EmptyPattern),
+            TagRule::new(RuleKind::Host, "  ", parse_tag_list("code"))
+        );
+        assert_eq!(
+            Err(RuleError::NoTags),
+            TagRule::new(RuleKind::Host, "github.com", parse_tag_list(" , "))
+        );
+        assert!(matches!(
+            TagRule::new(RuleKind::TitleRegex, "(unclosed", parse_tag_list("x")),
+            Err(RuleError::InvalidRegex(_))
+        ));
+    }
+}
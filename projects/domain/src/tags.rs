@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Tag {
+    pub uuid: Uuid,
+    pub name: String,
+}
+
+impl Tag {
+    pub fn new(uuid: Uuid, name: String) -> Self {
+        Self { uuid, name }
+    }
+
+    /// Creates a new tag with a normalized name, returns `None` if nothing
+    /// is left of the name after normalizing.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = normalize_tag_name(name);
+        (!name.is_empty()).then(|| Self {
+            uuid: Uuid::new_v4(),
+            name,
+        })
+    }
+}
+
+/// Tags are compared by name, so "Rust", " rust " and "rust" all end up
+/// being the same tag. Whitespace inside of the name is replaced by a `-`.
+pub fn normalize_tag_name(name: &str) -> String {
+    name.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Parses a comma separated list of tag names like `"rust, Databases,to share"`,
+/// skipping empty entries and duplicates.
+pub fn parse_tag_list(input: &str) -> Vec<Tag> {
+    tags_from_names(input.split(','))
+}
+
+/// Creates a tag for each of the names, skipping empty names and names that
+/// normalize to an already created tag.
+pub fn tags_from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<Tag> {
+    let mut tags: Vec<Tag> = vec![];
+    for tag in names.into_iter().filter_map(Tag::from_name) {
+        if !tags.iter().any(|t| t.name.eq(&tag.name)) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tags::{Tag, normalize_tag_name, parse_tag_list};
+
+    #[test]
+    fn normalize_tag_name_lowercases_and_joins_words() {
+        assert_eq!("to-share", normalize_tag_name("  To   Share "));
+    }
+
+    #[test]
+    fn tag_from_empty_name_is_none() {
+        assert_eq!(None, Tag::from_name("   "));
+    }
+
+    #[test]
+    fn parse_tag_list_skips_empty_and_duplicates() {
+        let names = parse_tag_list("rust, Databases,, RUST ,to share")
+            .into_iter()
+            .map(|t| t.name)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["rust", "databases", "to-share"], names);
+    }
+}
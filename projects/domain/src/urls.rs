@@ -0,0 +1,286 @@
+use std::fmt::Display;
+
+use url::{ParseError, Url, form_urlencoded};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    Parse(ParseError),
+    /// Only `http` and `https` links can be collected
+    UnsupportedScheme(String),
+}
+
+impl Display for UrlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UrlError::Parse(err) => write!(f, "{err}"),
+            UrlError::UnsupportedScheme(scheme) => write!(f, "unsupported scheme: {scheme}"),
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+impl From<ParseError> for UrlError {
+    fn from(value: ParseError) -> Self {
+        UrlError::Parse(value)
+    }
+}
+
+/// Query parameters that only exist to track where a click came from and
+/// never change the content of the page.
+const TRACKING_PARAMS: [&str; 11] = [
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid", "ref_src",
+];
+
+/// Normalizes a url so that different spellings of the same link compare equal.
+///
+/// - the scheme and host are lowercased and default ports removed
+/// - the fragment is removed
+/// - tracking parameters like `utm_*` and `fbclid` are removed
+/// - a trailing slash at the end of the path is removed
+///
+/// Fails for everything that isn't an absolute `http` or `https` url.
+pub fn normalize_url(url: &str) -> Result<String, UrlError> {
+    let mut url = Url::parse(url.trim())?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(UrlError::UnsupportedScheme(url.scheme().into()));
+    }
+    url.set_fragment(None);
+
+    let params = url
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+
+    if params.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    let path = url.path().trim_end_matches('/').to_owned();
+    url.set_path(&path);
+
+    let mut normalized = url.to_string();
+    // urls with a host always keep a `/` as path, so the slash of an
+    // otherwise empty path has to be removed from the string
+    if url.query().is_none() && normalized.ends_with('/') {
+        normalized.pop();
+    }
+
+    Ok(normalized)
+}
+
+/// The value used to detect duplicate articles, falls back to the trimmed
+/// input for everything that isn't a valid url.
+pub fn comparable_url(url: &str) -> String {
+    normalize_url(url).unwrap_or_else(|_| url.trim().to_owned())
+}
+
+/// Hosts of url shorteners, their links only redirect to the actual page.
+const SHORT_LINK_HOSTS: [&str; 16] = [
+    "t.co",
+    "bit.ly",
+    "redd.it",
+    "goo.gl",
+    "tinyurl.com",
+    "ow.ly",
+    "buff.ly",
+    "dlvr.it",
+    "lnkd.in",
+    "is.gd",
+    "t.ly",
+    "rebrand.ly",
+    "shorturl.at",
+    "trib.al",
+    "amzn.to",
+    "youtu.be",
+];
+
+/// Whether the url is a short link or an AMP version of a page, which lead
+/// to the actual article only through redirects or the canonical link of
+/// the page.
+pub fn needs_resolving(url: &str) -> bool {
+    let Ok(url) = Url::parse(url.trim()) else {
+        return false;
+    };
+    let Some(host) = url.host_str().map(str::to_lowercase) else {
+        return false;
+    };
+    SHORT_LINK_HOSTS.contains(&host.trim_start_matches("www.")) || is_amp_page(&url)
+}
+
+/// Whether the url is an AMP version of a page, only its canonical link
+/// leads to the actual article.
+pub fn is_amp(url: &str) -> bool {
+    Url::parse(url.trim()).is_ok_and(|url| is_amp_page(&url))
+}
+
+fn is_amp_page(url: &Url) -> bool {
+    let Some(host) = url.host_str().map(str::to_lowercase) else {
+        return false;
+    };
+    let host = host.trim_start_matches("www.");
+
+    host.starts_with("amp.")
+        || host.ends_with(".ampproject.org")
+        || url
+            .path_segments()
+            .is_some_and(|mut segments| segments.any(|s| s == "amp" || s.ends_with(".amp")))
+        || url
+            .query_pairs()
+            .any(|(key, value)| key == "amp" || (key == "outputType" && value == "amp"))
+}
+
+/// The path followed by the pairs as percent encoded query, e.g. for links
+/// to the article list filtered by a tag like `c++`.
+pub fn with_query<'a>(path: &str, pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish();
+    match query.is_empty() {
+        true => path.to_string(),
+        false => format!("{path}?{query}"),
+    }
+}
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::urls::{UrlError, is_amp, needs_resolving, normalize_url, with_query};
+
+    #[test]
+    fn normalize_url_lowercases_host() {
+        let res = normalize_url("https://GitHub.COM/mrkline/modern-latex").unwrap();
+        assert_eq!("https://github.com/mrkline/modern-latex", res);
+    }
+
+    #[test]
+    fn normalize_url_keeps_path_case() {
+        let res = normalize_url("https://github.com/Mrkline/Modern-Latex").unwrap();
+        assert_eq!("https://github.com/Mrkline/Modern-Latex", res);
+    }
+
+    #[test]
+    fn normalize_url_drops_trailing_slash() {
+        let res = normalize_url("https://www.nasa.gov/centers-and-facilities/stennis/").unwrap();
+        assert_eq!("https://www.nasa.gov/centers-and-facilities/stennis", res);
+    }
+
+    #[test]
+    fn normalize_url_drops_slash_of_root() {
+        let res = normalize_url("https://example.com/").unwrap();
+        assert_eq!("https://example.com", res);
+    }
+
+    #[test]
+    fn normalize_url_removes_fragment() {
+        let res = normalize_url("https://example.com/post#comments").unwrap();
+        assert_eq!("https://example.com/post", res);
+    }
+
+    #[test]
+    fn normalize_url_strips_tracking_params() {
+        let res = normalize_url(
+            "https://example.com/post?utm_source=bsky&id=4&UTM_Medium=social&fbclid=abc",
+        )
+        .unwrap();
+        assert_eq!("https://example.com/post?id=4", res);
+    }
+
+    #[test]
+    fn normalize_url_removes_empty_query() {
+        let res = normalize_url("https://example.com/post/?utm_campaign=x").unwrap();
+        assert_eq!("https://example.com/post", res);
+    }
+
+    #[test]
+    fn normalize_url_removes_default_port_and_whitespace() {
+        let res = normalize_url("  https://example.com:443/post  ").unwrap();
+        assert_eq!("https://example.com/post", res);
+    }
+
+    #[test]
+    fn normalize_url_rejects_text() {
+        assert!(normalize_url("not a url").is_err());
+    }
+
+    #[test]
+    fn normalize_url_rejects_other_schemes() {
+        assert_eq!(
+            Err(UrlError::UnsupportedScheme(String::from("mailto"))),
+            normalize_url("mailto:someone@example.com")
+        );
+    }
+
+    #[test]
+    fn short_links_need_resolving() {
+        for url in [
+            "https://t.co/abc123",
+            "https://bit.ly/3xyz",
+            "https://redd.it/1abcd",
+            "https://www.tinyurl.com/xyz",
+        ] {
+            assert!(needs_resolving(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn amp_pages_need_resolving() {
+        for url in [
+            "https://www.example.com/amp/news/story",
+            "https://example.com/news/story.amp",
+            "https://amp.example.com/news/story",
+            "https://example-com.cdn.ampproject.org/c/s/example.com/news/story",
+            "https://example.com/news/story?amp",
+        ] {
+            assert!(needs_resolving(url), "{url}");
+            assert!(is_amp(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn short_links_are_no_amp_pages() {
+        assert!(!is_amp("https://t.co/abc123"));
+        assert!(!is_amp("https://example.com/news/story"));
+    }
+
+    #[test]
+    fn other_links_need_no_resolving() {
+        for url in [
+            "https://example.com/news/story",
+            "https://example.com/ampersand",
+            "https://t.com/abc",
+            "not a url",
+        ] {
+            assert!(!needs_resolving(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn with_query_encodes_values() {
+        assert_eq!(
+            "/articles?tag=c%2B%2B&read=all",
+            with_query("/articles", [("tag", "c++"), ("read", "all")])
+        );
+        assert_eq!(
+            "/articles?tag=r%26d",
+            with_query("/articles", [("tag", "r&d")])
+        );
+        assert_eq!(
+            "/articles?tag=c%23",
+            with_query("/articles", [("tag", "c#")])
+        );
+    }
+
+    #[test]
+    fn with_query_without_pairs_is_the_path() {
+        assert_eq!("/articles", with_query("/articles", []));
+    }
+}
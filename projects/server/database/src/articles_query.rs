@@ -1,49 +1,551 @@
-use domain::articles::Article;
-use sea_orm::{ConnectionTrait, DbErr, EntityTrait, IntoActiveModel};
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use domain::{
+    articles::{Article, ArticleFilter},
+    pagination::{ArticlePage, Cursor},
+    reading_time::ReadingTime,
+    submission::{LineOutcome, SubmissionEntry, SubmissionReport},
+    urls::comparable_url,
+};
+use sea_orm::{
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, LoaderTrait,
+    QueryFilter, QueryOrder, QuerySelect, Select, TransactionTrait, TryInsertResult,
+    sea_query::{Expr, OnConflict, Order, Query, SelectStatement},
+};
 use uuid::Uuid;
 
-use crate::entities::articles;
+use crate::{
+    INSERT_CHUNK_SIZE,
+    entities::{
+        article_tags,
+        articles::{self, reading_time_columns},
+        tags,
+    },
+    jobs_query, link_checks_query,
+    tags_query::{self, tagged_article_uuids},
+};
 
 pub async fn all<C>(db: &C) -> Result<Vec<Article>, DbErr>
 where
     C: ConnectionTrait,
 {
-    articles::Entity::find()
+    all_matching(db, &ArticleFilter::default()).await
+}
+
+/// All articles matching the filter, newest first and without pagination.
+pub async fn all_matching<C>(db: &C, filter: &ArticleFilter) -> Result<Vec<Article>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let models = filtered(filter)
+        .order_by_desc(articles::Column::CreatedAt)
         .all(db)
-        .await
-        .map(|art| art.into_iter().map(Into::into).collect())
+        .await?;
+
+    with_details(models, db).await
+}
+
+/// Keyset paginated list of the articles matching the filter, newest first.
+/// Pass the `next` cursor of the previous page to get the following one.
+pub async fn page<C>(
+    db: &C,
+    filter: &ArticleFilter,
+    after: Option<Cursor>,
+    page_size: u64,
+) -> Result<ArticlePage, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut select = filtered(filter);
+
+    if let Some(Cursor { created_at, uuid }) = after {
+        select = select.filter(
+            Condition::any()
+                .add(articles::Column::CreatedAt.lt(created_at))
+                .add(
+                    Condition::all()
+                        .add(articles::Column::CreatedAt.eq(created_at))
+                        .add(articles::Column::Uuid.lt(uuid)),
+                ),
+        );
+    }
+
+    let models = select
+        .order_by_desc(articles::Column::CreatedAt)
+        .order_by_desc(articles::Column::Uuid)
+        .limit(page_size + 1)
+        .all(db)
+        .await?;
+
+    let articles = with_details(models, db).await?;
+    Ok(ArticlePage::from_overfetched(articles, page_size as usize))
 }
 
 pub async fn one<C>(db: &C, uuid: Uuid) -> Result<Option<Article>, DbErr>
 where
     C: ConnectionTrait,
 {
-    articles::Entity::find_by_id(uuid)
+    let models = not_trashed()
+        .filter(articles::Column::Uuid.eq(uuid))
+        .all(db)
+        .await?;
+
+    Ok(with_details(models, db).await?.pop())
+}
+
+/// How many search results are returned at most
+pub const SEARCH_LIMIT: u64 = 100;
+
+/// Full text search over the title, description and url of the articles
+/// matching the filter, the [`SEARCH_LIMIT`] best matches first. The query
+/// supports the `websearch_to_tsquery` syntax meaning `"quoted phrases"`,
+/// `or` and `-excluded` words.
+pub async fn search<C>(db: &C, query: &str, filter: &ArticleFilter) -> Result<Vec<Article>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let models = filtered(filter)
+        .filter(Expr::cust_with_values(
+            format!("search_vector @@ {SEARCH_QUERY}"),
+            [query],
+        ))
+        .order_by(
+            Expr::cust_with_values(format!("ts_rank(search_vector, {SEARCH_QUERY})"), [query]),
+            Order::Desc,
+        )
+        .order_by_desc(articles::Column::CreatedAt)
+        .limit(SEARCH_LIMIT)
+        .all(db)
+        .await?;
+
+    with_details(models, db).await
+}
+
+/// Titles and descriptions are stemmed in the `search_vector` while urls are
+/// not, so the query has to match either of the two.
+const SEARCH_QUERY: &str =
+    "(websearch_to_tsquery('english', $1) || websearch_to_tsquery('simple', $1))";
+
+/// Whether another article than `except` has the url, trashed articles
+/// included.
+pub async fn is_url_taken<C>(db: &C, url: &str, except: Uuid) -> Result<bool, DbErr>
+where
+    C: ConnectionTrait,
+{
+    Ok(uuid_by_url(db, url, except).await?.is_some())
+}
+
+/// The uuid of another article than `except` with the url, trashed articles
+/// included.
+pub async fn uuid_by_url<C>(db: &C, url: &str, except: Uuid) -> Result<Option<Uuid>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    articles::Entity::find()
+        .select_only()
+        .column(articles::Column::Uuid)
+        .filter(articles::Column::NormalizedUrl.eq(comparable_url(url)))
+        .filter(articles::Column::Uuid.ne(except))
+        .into_tuple::<Uuid>()
         .one(db)
         .await
-        .map(|opt_art| opt_art.map(|art| art.into()))
 }
 
-pub async fn insert_many<C>(articles: Vec<Article>, db: &C) -> Result<(), DbErr>
+/// Inserts all articles whose normalized url isn't stored yet. Returns the
+/// articles that were skipped because they are duplicates, either of an
+/// already stored article or of an earlier one in the passed list.
+///
+/// Duplicates are left to the unique index instead of being looked up
+/// before, so articles inserted concurrently, e.g. by the feed poller, are
+/// reported as duplicates as well. The articles are inserted together with
+/// their tags or not at all, large imports in several statements.
+pub async fn insert_many<C>(articles: Vec<Article>, db: &C) -> Result<Vec<Article>, DbErr>
 where
-    C: ConnectionTrait,
+    C: ConnectionTrait + TransactionTrait,
 {
-    let entities = articles
+    if articles.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let txn = db.begin().await?;
+    let mut inserted = HashSet::new();
+    for chunk in articles.chunks(INSERT_CHUNK_SIZE) {
+        let entities = chunk
+            .iter()
+            .map(|a| articles::Model::from(a.clone()).into_active_model());
+
+        // trashed articles keep their url, restoring them could otherwise
+        // create duplicates
+        let result = articles::Entity::insert_many(entities)
+            .on_conflict(
+                OnConflict::column(articles::Column::NormalizedUrl)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec_with_returning_keys(&txn)
+            .await?;
+        if let TryInsertResult::Inserted(uuids) = result {
+            inserted.extend(uuids);
+        }
+    }
+
+    let (new, duplicates): (Vec<_>, Vec<_>) = articles
         .into_iter()
-        .map(|a| articles::Model::from(a).into_active_model());
+        .partition(|a| inserted.contains(&a.uuid));
 
-    articles::Entity::insert_many(entities)
-        .exec(db)
-        .await
-        .map(|_| ())
+    for article in new {
+        tags_query::set_for_article(article.uuid, article.tags, &txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(duplicates)
+}
+
+/// Inserts the articles of the entries through [`insert_many`] and reports
+/// what happened to each of the entries.
+pub async fn insert_reported<C>(
+    entries: Vec<SubmissionEntry>,
+    db: &C,
+) -> Result<SubmissionReport, DbErr>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let articles = entries
+        .iter()
+        .filter_map(|(_, _, article)| article.as_ref().ok().cloned())
+        .collect();
+
+    let duplicates = insert_many(articles, db)
+        .await?
+        .into_iter()
+        .map(|a| a.uuid)
+        .collect::<HashSet<_>>();
+
+    let mut report = SubmissionReport::default();
+    for (number, line, outcome) in entries {
+        let outcome = match outcome {
+            Ok(article) if duplicates.contains(&article.uuid) => LineOutcome::Duplicate,
+            Ok(_) => LineOutcome::Inserted,
+            Err(outcome) => outcome,
+        };
+        report.push(number, &line, outcome);
+    }
+    Ok(report)
+}
+
+/// Stores the changed title, url, original url, description, source and
+/// tags of the article together. The creation time is kept and `updated_at`
+/// is set to now.
+pub async fn update<C>(article: Article, db: &C) -> Result<Article, DbErr>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let tags = article.tags.clone();
+    let articles::Model {
+        uuid,
+        title,
+        url,
+        normalized_url,
+        original_url,
+        description,
+        found_via,
+        ..
+    } = articles::Model::from(article);
+
+    let txn = db.begin().await?;
+    articles::Entity::update(articles::ActiveModel {
+        uuid: Unchanged(uuid),
+        title: Set(title),
+        url: Set(url),
+        normalized_url: Set(normalized_url),
+        original_url: Set(original_url),
+        description: Set(description),
+        found_via: Set(found_via),
+        updated_at: Set(Utc::now()),
+        ..Default::default()
+    })
+    .exec(&txn)
+    .await?;
+
+    tags_query::set_for_article(uuid, tags, &txn).await?;
+    txn.commit().await?;
+
+    one(db, uuid).await?.ok_or(DbErr::RecordNotUpdated)
+}
+
+/// Marks the article as read now or as unread again, returns the changed
+/// article. Reading isn't a change to the content so `updated_at` is kept.
+pub async fn set_read<C>(article_uuid: Uuid, read: bool, db: &C) -> Result<Article, DbErr>
+where
+    C: ConnectionTrait,
+{
+    articles::Entity::update(articles::ActiveModel {
+        uuid: Unchanged(article_uuid),
+        read_at: Set(read.then(Utc::now)),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?;
+
+    one(db, article_uuid).await?.ok_or(DbErr::RecordNotUpdated)
 }
 
-pub async fn delete<C>(article_uuid: Uuid, db: &C) -> Result<(), DbErr>
+/// Stores the hashes of the favicon and preview image of the article,
+/// `updated_at` is kept.
+pub async fn set_images<C>(
+    article_uuid: Uuid,
+    favicon: Option<String>,
+    preview_image: Option<String>,
+    db: &C,
+) -> Result<(), DbErr>
 where
     C: ConnectionTrait,
 {
-    articles::Entity::delete_by_id(article_uuid)
+    articles::Entity::update(articles::ActiveModel {
+        uuid: Unchanged(article_uuid),
+        favicon: Set(favicon),
+        preview_image: Set(preview_image),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?;
+    Ok(())
+}
+
+/// Stores the length of the article's content, `updated_at` is kept.
+pub async fn set_reading_time<C>(
+    article_uuid: Uuid,
+    reading_time: ReadingTime,
+    db: &C,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let (word_count, reading_minutes) = reading_time_columns(reading_time);
+    articles::Entity::update(articles::ActiveModel {
+        uuid: Unchanged(article_uuid),
+        word_count: Set(Some(word_count)),
+        reading_minutes: Set(Some(reading_minutes)),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?;
+    Ok(())
+}
+
+/// Articles in the trash, most recently trashed first.
+pub async fn trashed<C>(db: &C) -> Result<Vec<Article>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let models = articles::Entity::find()
+        .filter(articles::Column::DeletedAt.is_not_null())
+        .order_by_desc(articles::Column::DeletedAt)
+        .all(db)
+        .await?;
+
+    with_details(models, db).await
+}
+
+/// Moves the article into the trash, it is hidden from all other queries
+/// until it is restored.
+pub async fn trash<C>(article_uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    set_deleted_at(article_uuid, Some(Utc::now()), db).await
+}
+
+/// Adds the tags of the duplicate to the article and moves the duplicate
+/// into the trash, together or not at all.
+pub async fn merge_duplicate<C>(
+    duplicate_uuid: Uuid,
+    article_uuid: Uuid,
+    db: &C,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    let txn = db.begin().await?;
+    let mut tags = tags_query::for_article(&txn, article_uuid).await?;
+    for tag in tags_query::for_article(&txn, duplicate_uuid).await? {
+        if !tags.iter().any(|kept| kept.uuid == tag.uuid) {
+            tags.push(tag);
+        }
+    }
+    tags_query::set_for_article(article_uuid, tags, &txn).await?;
+    trash(duplicate_uuid, &txn).await?;
+    txn.commit().await
+}
+
+pub async fn restore<C>(article_uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    set_deleted_at(article_uuid, None, db).await
+}
+
+/// Permanently deletes the article, only articles that are already in the
+/// trash can be purged.
+pub async fn purge<C>(article_uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    articles::Entity::delete_many()
+        .filter(articles::Column::Uuid.eq(article_uuid))
+        .filter(articles::Column::DeletedAt.is_not_null())
         .exec(db)
         .await
         .map(|_| ())
 }
+
+async fn set_deleted_at<C>(
+    article_uuid: Uuid,
+    deleted_at: Option<DateTime<Utc>>,
+    db: &C,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    articles::Entity::update(articles::ActiveModel {
+        uuid: Unchanged(article_uuid),
+        deleted_at: Set(deleted_at),
+        ..Default::default()
+    })
+    .exec(db)
+    .await
+    .map(|_| ())
+}
+
+/// All articles that are not in the trash, every query that reads articles
+/// for the user should start from here.
+pub(crate) fn not_trashed() -> Select<articles::Entity> {
+    articles::Entity::find().filter(articles::Column::DeletedAt.is_null())
+}
+
+/// The uuids of the articles that aren't in the trash, for hiding what
+/// belongs to trashed articles.
+pub(crate) fn not_trashed_uuids() -> SelectStatement {
+    Query::select()
+        .column(articles::Column::Uuid)
+        .from(articles::Entity)
+        .and_where(articles::Column::DeletedAt.is_null())
+        .to_owned()
+}
+
+fn filtered(filter: &ArticleFilter) -> Select<articles::Entity> {
+    let mut select = not_trashed();
+
+    if let Some(tag) = &filter.tag {
+        select = select.filter(articles::Column::Uuid.in_subquery(tagged_article_uuids(tag)));
+    }
+
+    if filter.unread_only {
+        select = select.filter(articles::Column::ReadAt.is_null());
+    }
+
+    if let Some(max_minutes) = filter.max_minutes {
+        select = select.filter(articles::Column::ReadingMinutes.lte(max_minutes));
+    }
+
+    select
+}
+
+/// Loads the tags of all the passed articles and converts them into domain
+/// articles while keeping their order.
+pub(crate) async fn with_tags<C>(
+    models: Vec<articles::Model>,
+    db: &C,
+) -> Result<Vec<Article>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let tags = models
+        .load_many_to_many(tags::Entity, article_tags::Entity, db)
+        .await?;
+
+    Ok(models
+        .into_iter()
+        .zip(tags)
+        .map(|(model, tags)| {
+            Article::from(model).with_tags(tags.into_iter().map(Into::into).collect())
+        })
+        .collect())
+}
+
+/// Like [`with_tags`] and also loads the enrichment status and the link
+/// health of the articles, for showing them.
+pub(crate) async fn with_details<C>(
+    models: Vec<articles::Model>,
+    db: &C,
+) -> Result<Vec<Article>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let uuids = models.iter().map(|m| m.uuid).collect::<Vec<_>>();
+    let mut statuses = jobs_query::statuses(db, &uuids).await?;
+    let mut checks = link_checks_query::checks(db, &uuids).await?;
+
+    Ok(with_tags(models, db)
+        .await?
+        .into_iter()
+        .map(|mut article| {
+            article.enrichment = statuses.remove(&article.uuid);
+            article.link_health = checks
+                .remove(&article.uuid)
+                .and_then(|check| check.health(&article.url));
+            article
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use domain::articles::Article;
+    use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+
+    use crate::{INSERT_CHUNK_SIZE, articles_query::insert_many};
+
+    #[tokio::test]
+    async fn large_imports_are_inserted_in_chunks() {
+        let articles = (0..=INSERT_CHUNK_SIZE)
+            .map(|i| {
+                Article::from_parts(format!("Article {i}"), format!("https://example.com/{i}"))
+            })
+            .collect::<Vec<_>>();
+        let returned = articles
+            .chunks(INSERT_CHUNK_SIZE)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(|a| BTreeMap::from([("uuid", Value::from(a.uuid))]))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // the tags of every inserted article are replaced
+        let tag_deletes = articles.iter().map(|_| MockExecResult {
+            last_insert_id: 0,
+            rows_affected: 0,
+        });
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results(returned)
+            .append_exec_results(tag_deletes)
+            .into_connection();
+
+        let duplicates = insert_many(articles, &db).await.unwrap();
+        assert!(duplicates.is_empty());
+
+        let inserts = db
+            .into_transaction_log()
+            .iter()
+            .flat_map(|transaction| transaction.statements())
+            .filter(|statement| statement.sql.starts_with(r#"INSERT INTO "articles""#))
+            .count();
+        assert_eq!(2, inserts);
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::Utc;
+use domain::reader::ArticleContent;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::{articles_query::not_trashed_uuids, entities::article_contents};
+
+/// The extracted content of the article, articles in the trash have none.
+pub async fn one<C>(db: &C, article_uuid: Uuid) -> Result<Option<ArticleContent>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    article_contents::Entity::find_by_id(article_uuid)
+        .filter(article_contents::Column::ArticleUuid.in_subquery(not_trashed_uuids()))
+        .one(db)
+        .await
+        .map(|content| content.map(Into::into))
+}
+
+/// Stores the content of the article, replacing content that was extracted
+/// before.
+pub async fn store<C>(article_uuid: Uuid, content: ArticleContent, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let ArticleContent { html, text } = content;
+    article_contents::Entity::insert(article_contents::ActiveModel {
+        article_uuid: Set(article_uuid),
+        html: Set(html),
+        text: Set(text),
+        extracted_at: Set(Utc::now()),
+    })
+    .on_conflict(
+        OnConflict::column(article_contents::Column::ArticleUuid)
+            .update_columns([
+                article_contents::Column::Html,
+                article_contents::Column::Text,
+                article_contents::Column::ExtractedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(db)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::reader::ArticleContent;
+    use sea_orm::{DatabaseBackend, MockDatabase};
+    use uuid::Uuid;
+
+    use crate::{contents_query::one, entities::article_contents};
+
+    #[tokio::test]
+    async fn contents_of_trashed_articles_are_hidden() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<article_contents::Model>::new()])
+            .into_connection();
+
+        let content: Option<ArticleContent> = one(&db, Uuid::new_v4()).await.unwrap();
+        assert_eq!(None, content);
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.contains(r#""deleted_at" IS NULL"#), "{sql}");
+    }
+}
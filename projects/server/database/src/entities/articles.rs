@@ -1,6 +1,7 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
-use domain::articles::Article;
+use chrono::{DateTime, Utc};
+use domain::{articles::Article, reading_time::ReadingTime, urls::comparable_url};
 use sea_orm::entity::prelude::*;
 use uuid::Uuid;
 
@@ -11,27 +12,150 @@ pub struct Model {
     pub uuid: Uuid,
     pub title: String,
     pub url: String,
+    /// Used to detect duplicates, only `None` for duplicates that existed
+    /// before urls were normalized
+    #[sea_orm(unique)]
+    pub normalized_url: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub original_url: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub found_via: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub favicon: Option<String>,
+    pub preview_image: Option<String>,
+    pub word_count: Option<i32>,
+    pub reading_minutes: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::article_tags::Entity")]
+    ArticleTags,
+}
+
+impl Related<super::article_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ArticleTags.def()
+    }
+}
+
+impl Related<super::tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::article_tags::Relation::Tags.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::article_tags::Relation::Articles.def().rev())
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
 
 impl From<Model> for Article {
-    fn from(Model { uuid, title, url }: Model) -> Self {
-        Article::new(uuid, title, url)
+    fn from(
+        Model {
+            uuid,
+            title,
+            url,
+            normalized_url: _,
+            original_url,
+            description,
+            found_via,
+            created_at,
+            updated_at,
+            deleted_at,
+            read_at,
+            favicon,
+            preview_image,
+            word_count,
+            reading_minutes,
+        }: Model,
+    ) -> Self {
+        let reading_time = word_count
+            .zip(reading_minutes)
+            .map(|(words, minutes)| ReadingTime {
+                words: u32::try_from(words).unwrap_or_default(),
+                minutes: u32::try_from(minutes).unwrap_or_default(),
+            });
+
+        Article {
+            uuid,
+            title,
+            url,
+            original_url,
+            description,
+            found_via,
+            created_at,
+            updated_at,
+            deleted_at,
+            read_at,
+            tags: vec![],
+            favicon,
+            preview_image,
+            reading_time,
+            enrichment: None,
+            link_health: None,
+        }
     }
 }
 
 impl From<Article> for Model {
-    fn from(Article { uuid, title, url }: Article) -> Self {
-        Model { uuid, title, url }
+    fn from(
+        Article {
+            uuid,
+            title,
+            url,
+            original_url,
+            description,
+            found_via,
+            created_at,
+            updated_at,
+            deleted_at,
+            read_at,
+            tags: _,
+            favicon,
+            preview_image,
+            reading_time,
+            enrichment: _,
+            link_health: _,
+        }: Article,
+    ) -> Self {
+        Model {
+            uuid,
+            title,
+            normalized_url: Some(comparable_url(&url)),
+            url,
+            original_url,
+            description,
+            found_via,
+            created_at,
+            updated_at,
+            deleted_at,
+            read_at,
+            favicon,
+            preview_image,
+            word_count: reading_time.map(|time| reading_time_columns(time).0),
+            reading_minutes: reading_time.map(|time| reading_time_columns(time).1),
+        }
     }
 }
 
+/// The values of the `word_count` and `reading_minutes` columns.
+pub(crate) fn reading_time_columns(reading_time: ReadingTime) -> (i32, i32) {
+    let saturating = |value: u32| i32::try_from(value).unwrap_or(i32::MAX);
+    (
+        saturating(reading_time.words),
+        saturating(reading_time.minutes),
+    )
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
     use domain::articles::Article;
     use uuid::Uuid;
 
@@ -56,6 +180,18 @@ mod tests {
             uuid: Uuid::new_v4(),
             title: String::from("title"),
             url: String::from("url"),
+            normalized_url: Some(String::from("url")),
+            original_url: Some(String::from("original_url")),
+            description: Some(String::from("description")),
+            found_via: Some(String::from("found_via")),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            read_at: None,
+            favicon: Some(String::from("favicon")),
+            preview_image: None,
+            word_count: Some(1200),
+            reading_minutes: Some(6),
         }
     }
 
@@ -2,4 +2,13 @@
 
 pub mod prelude;
 
+pub mod article_contents;
+pub mod article_tags;
 pub mod articles;
+pub mod feeds;
+pub mod images;
+pub mod jobs;
+pub mod link_checks;
+pub mod snapshots;
+pub mod tag_rules;
+pub mod tags;
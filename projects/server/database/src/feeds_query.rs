@@ -0,0 +1,74 @@
+use chrono::Utc;
+use domain::feeds::Feed;
+use sea_orm::{
+    ActiveValue::{Set, Unchanged},
+    ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryOrder,
+};
+use uuid::Uuid;
+
+use crate::entities::feeds;
+
+pub async fn all<C>(db: &C) -> Result<Vec<Feed>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    feeds::Entity::find()
+        .order_by_asc(feeds::Column::CreatedAt)
+        .all(db)
+        .await
+        .map(|feeds| feeds.into_iter().map(Into::into).collect())
+}
+
+pub async fn one<C>(db: &C, uuid: Uuid) -> Result<Option<Feed>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    feeds::Entity::find_by_id(uuid)
+        .one(db)
+        .await
+        .map(|feed| feed.map(Into::into))
+}
+
+pub async fn insert<C>(feed: Feed, db: &C) -> Result<Feed, DbErr>
+where
+    C: ConnectionTrait,
+{
+    feeds::Entity::insert(feeds::Model::from(feed.clone()).into_active_model())
+        .exec(db)
+        .await?;
+    Ok(feed)
+}
+
+pub async fn delete<C>(uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    feeds::Entity::delete_by_id(uuid).exec(db).await?;
+    Ok(())
+}
+
+/// Stores the outcome of polling the feed now, the title is only set if
+/// the feed has one.
+pub async fn set_polled<C>(
+    uuid: Uuid,
+    title: Option<String>,
+    error: Option<String>,
+    db: &C,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    feeds::Entity::update(feeds::ActiveModel {
+        uuid: Unchanged(uuid),
+        title: match title {
+            Some(title) => Set(Some(title)),
+            None => Default::default(),
+        },
+        last_polled_at: Set(Some(Utc::now())),
+        last_error: Set(error),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use domain::images::StoredImage;
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, sea_query::OnConflict};
+
+use crate::entities::images;
+
+pub async fn one<C>(db: &C, hash: &str) -> Result<Option<StoredImage>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    images::Entity::find_by_id(hash)
+        .one(db)
+        .await
+        .map(|image| image.map(Into::into))
+}
+
+/// Stores the image unless an image with the same hash is stored already.
+pub async fn store<C>(image: StoredImage, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    images::Entity::insert(images::Model::from(image).into_active_model())
+        .on_conflict(
+            OnConflict::column(images::Column::Hash)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(db)
+        .await?;
+    Ok(())
+}
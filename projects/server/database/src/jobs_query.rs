@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use domain::jobs::{EnrichmentStatus, Job, JobKind};
+use sea_orm::{
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, ConnectionTrait, DbBackend, DbErr, EntityTrait, IntoActiveModel, QueryFilter,
+    QuerySelect, Statement,
+};
+use uuid::Uuid;
+
+use crate::{
+    INSERT_CHUNK_SIZE,
+    entities::{articles, jobs},
+};
+
+/// Queues a job of each of the kinds for each of the articles, they run as
+/// soon as a worker gets to them. Articles that don't exist, like duplicates
+/// that were skipped on insert, are ignored. Many articles are queued in
+/// several statements.
+pub async fn enqueue<C>(article_uuids: Vec<Uuid>, kinds: &[JobKind], db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    if kinds.is_empty() {
+        return Ok(());
+    }
+
+    for chunk in article_uuids.chunks(INSERT_CHUNK_SIZE) {
+        let existing = articles::Entity::find()
+            .select_only()
+            .column(articles::Column::Uuid)
+            .filter(articles::Column::Uuid.is_in(chunk.iter().copied()))
+            .into_tuple::<Uuid>()
+            .all(db)
+            .await?;
+
+        let jobs = existing
+            .into_iter()
+            .flat_map(|uuid| kinds.iter().map(move |kind| Job::new(uuid, *kind)))
+            .collect::<Vec<_>>();
+        for jobs in jobs.chunks(INSERT_CHUNK_SIZE) {
+            let jobs = jobs
+                .iter()
+                .map(|job| jobs::Model::from(job.clone()).into_active_model());
+            jobs::Entity::insert_many(jobs).exec(db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Takes up to `limit` due jobs, oldest first. Taking a job counts as an
+/// attempt and makes it due again only after the `lease`, so the jobs of a
+/// worker that died are picked up again later. Jobs taken by another worker
+/// are skipped.
+pub async fn take_due<C>(limit: u64, lease: Duration, db: &C) -> Result<Vec<Job>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let now = Utc::now();
+    let statement = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"UPDATE jobs SET attempts = attempts + 1, run_after = $1
+        WHERE uuid IN (
+            SELECT uuid FROM jobs
+            WHERE failed_at IS NULL AND run_after <= $2
+            ORDER BY run_after
+            LIMIT $3
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *"#,
+        [(now + lease).into(), now.into(), (limit as i64).into()],
+    );
+
+    jobs::Entity::find()
+        .from_raw_sql(statement)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(Job::try_from)
+        .collect()
+}
+
+/// Removes the finished job from the queue.
+pub async fn complete<C>(job_uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    jobs::Entity::delete_by_id(job_uuid).exec(db).await?;
+    Ok(())
+}
+
+/// Stores when a failed job is retried, or that it won't be, see
+/// [`Job::failed`].
+pub async fn save_failure<C>(job: Job, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    jobs::Entity::update(jobs::ActiveModel {
+        uuid: Unchanged(job.uuid),
+        run_after: Set(job.run_after),
+        last_error: Set(job.last_error),
+        failed_at: Set(job.failed_at),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?;
+    Ok(())
+}
+
+/// Queues the failed jobs of the article again with all their attempts.
+pub async fn retry_failed<C>(article_uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    jobs::Entity::update_many()
+        .col_expr(jobs::Column::Attempts, 0.into())
+        .col_expr(jobs::Column::RunAfter, Utc::now().into())
+        .col_expr(
+            jobs::Column::FailedAt,
+            Option::<chrono::DateTime<Utc>>::None.into(),
+        )
+        .filter(jobs::Column::ArticleUuid.eq(article_uuid))
+        .filter(jobs::Column::FailedAt.is_not_null())
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// The enrichment status of those of the articles that still have jobs.
+pub async fn statuses<C>(
+    db: &C,
+    article_uuids: &[Uuid],
+) -> Result<HashMap<Uuid, EnrichmentStatus>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    if article_uuids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut jobs_by_article = HashMap::<Uuid, Vec<Job>>::new();
+    for model in jobs::Entity::find()
+        .filter(jobs::Column::ArticleUuid.is_in(article_uuids.iter().copied()))
+        .all(db)
+        .await?
+    {
+        let job = Job::try_from(model)?;
+        jobs_by_article
+            .entry(job.article_uuid)
+            .or_default()
+            .push(job);
+    }
+
+    Ok(jobs_by_article
+        .into_iter()
+        .filter_map(|(uuid, jobs)| Some((uuid, EnrichmentStatus::of(&jobs)?)))
+        .collect())
+}
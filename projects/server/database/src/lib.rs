@@ -1,2 +1,15 @@
 pub mod articles_query;
+pub mod contents_query;
 mod entities;
+pub mod feeds_query;
+pub mod images_query;
+pub mod jobs_query;
+pub mod link_checks_query;
+pub mod snapshots_query;
+pub mod tag_rules_query;
+pub mod tags_query;
+
+/// How many rows are inserted by one statement. Postgres takes at most
+/// 65535 parameters per statement, chunks of this size stay far below that
+/// for every table.
+const INSERT_CHUNK_SIZE: usize = 500;
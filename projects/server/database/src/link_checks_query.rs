@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use domain::{articles::Article, link_health::LinkCheck};
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+    sea_query::{Expr, OnConflict, Query},
+};
+use uuid::Uuid;
+
+use crate::{
+    articles_query::{not_trashed, with_details},
+    entities::{articles, link_checks},
+};
+
+/// Up to `limit` articles whose link wasn't checked since `checked_before`,
+/// as their uuid and url, ordered by uuid. Articles whose url changed since
+/// the last check are due right away. Only articles after the uuid `after`
+/// are returned, so going through the due links in batches never returns
+/// the same link twice, even if storing its check failed.
+pub async fn due<C>(
+    db: &C,
+    checked_before: DateTime<Utc>,
+    after: Option<Uuid>,
+    limit: u64,
+) -> Result<Vec<(Uuid, String)>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let mut select = not_trashed();
+    if let Some(after) = after {
+        select = select.filter(articles::Column::Uuid.gt(after));
+    }
+    select
+        .select_only()
+        .column(articles::Column::Uuid)
+        .column(articles::Column::Url)
+        .filter(Expr::cust_with_values(
+            "NOT EXISTS (SELECT 1 FROM link_checks \
+            WHERE link_checks.article_uuid = articles.uuid \
+            AND link_checks.url = articles.url AND link_checks.checked_at >= $1)",
+            [checked_before],
+        ))
+        .order_by_asc(articles::Column::Uuid)
+        .limit(limit)
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+/// The latest checks of those of the articles that were checked.
+pub async fn checks<C>(db: &C, article_uuids: &[Uuid]) -> Result<HashMap<Uuid, LinkCheck>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    if article_uuids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(link_checks::Entity::find()
+        .filter(link_checks::Column::ArticleUuid.is_in(article_uuids.iter().copied()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|model| (model.article_uuid, model.into()))
+        .collect())
+}
+
+/// Articles whose link is broken or moved, newest first.
+pub async fn unhealthy<C>(db: &C) -> Result<Vec<Article>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let suspicious = Query::select()
+        .column(link_checks::Column::ArticleUuid)
+        .from(link_checks::Entity)
+        .cond_where(
+            Condition::any()
+                .add(link_checks::Column::Status.is_null())
+                .add(link_checks::Column::Status.gte(400))
+                .add(link_checks::Column::FinalUrl.is_not_null()),
+        )
+        .to_owned();
+
+    let models = not_trashed()
+        .filter(articles::Column::Uuid.in_subquery(suspicious))
+        .order_by_desc(articles::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    // redirects to the same page and outdated checks are only ruled out
+    // with the article
+    Ok(with_details(models, db)
+        .await?
+        .into_iter()
+        .filter(|article| article.link_health.is_some())
+        .collect())
+}
+
+/// Stores the check of the article, replacing the previous one.
+pub async fn store<C>(article_uuid: Uuid, check: LinkCheck, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let LinkCheck {
+        url,
+        status,
+        final_url,
+        checked_at,
+    } = check;
+    link_checks::Entity::insert(link_checks::ActiveModel {
+        article_uuid: Set(article_uuid),
+        url: Set(url),
+        status: Set(status.and_then(|status| i16::try_from(status).ok())),
+        final_url: Set(final_url),
+        checked_at: Set(checked_at),
+    })
+    .on_conflict(
+        OnConflict::column(link_checks::Column::ArticleUuid)
+            .update_columns([
+                link_checks::Column::Url,
+                link_checks::Column::Status,
+                link_checks::Column::FinalUrl,
+                link_checks::Column::CheckedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(db)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use domain::snapshots::Snapshot;
+use sea_orm::{
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use uuid::Uuid;
+
+use crate::{articles_query::not_trashed_uuids, entities::snapshots};
+
+/// The most recent snapshot of the article.
+pub async fn latest<C>(db: &C, article_uuid: Uuid) -> Result<Option<Snapshot>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    latest_model(db, article_uuid)
+        .await
+        .map(|snapshot| snapshot.map(Into::into))
+}
+
+/// When the most recent snapshot of the article was taken, without loading
+/// the snapshot itself. Articles in the trash have no snapshots.
+pub async fn latest_taken_at<C>(db: &C, article_uuid: Uuid) -> Result<Option<DateTime<Utc>>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    snapshots::Entity::find()
+        .select_only()
+        .column(snapshots::Column::TakenAt)
+        .filter(snapshots::Column::ArticleUuid.eq(article_uuid))
+        .filter(snapshots::Column::ArticleUuid.in_subquery(not_trashed_uuids()))
+        .order_by_desc(snapshots::Column::TakenAt)
+        .into_tuple()
+        .one(db)
+        .await
+}
+
+/// Stores the snapshot of the article. A page that didn't change since the
+/// latest snapshot isn't stored again, only the time of that snapshot is
+/// updated.
+pub async fn store<C>(article_uuid: Uuid, snapshot: Snapshot, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    if let Some(latest) = latest_model(db, article_uuid).await?
+        && latest.hash == snapshot.hash
+    {
+        snapshots::Entity::update(snapshots::ActiveModel {
+            uuid: Unchanged(latest.uuid),
+            taken_at: Set(snapshot.taken_at),
+            ..Default::default()
+        })
+        .exec(db)
+        .await?;
+        return Ok(());
+    }
+
+    let Snapshot {
+        html,
+        hash,
+        taken_at,
+    } = snapshot;
+    snapshots::Entity::insert(snapshots::ActiveModel {
+        uuid: Set(Uuid::new_v4()),
+        article_uuid: Set(article_uuid),
+        hash: Set(hash),
+        html: Set(html),
+        taken_at: Set(taken_at),
+    })
+    .exec(db)
+    .await?;
+    Ok(())
+}
+
+async fn latest_model<C>(db: &C, article_uuid: Uuid) -> Result<Option<snapshots::Model>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    snapshots::Entity::find()
+        .filter(snapshots::Column::ArticleUuid.eq(article_uuid))
+        .order_by_desc(snapshots::Column::TakenAt)
+        .one(db)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use sea_orm::{DatabaseBackend, MockDatabase, Value};
+    use uuid::Uuid;
+
+    use crate::snapshots_query::latest_taken_at;
+
+    #[tokio::test]
+    async fn snapshots_of_trashed_articles_are_hidden() {
+        let db = MockDatabase::new(DatabaseBackend::Postgres)
+            .append_query_results([Vec::<BTreeMap<&str, Value>>::new()])
+            .into_connection();
+
+        assert_eq!(None, latest_taken_at(&db, Uuid::new_v4()).await.unwrap());
+
+        let log = db.into_transaction_log();
+        let sql = &log[0].statements()[0].sql;
+        assert!(sql.contains(r#""deleted_at" IS NULL"#), "{sql}");
+    }
+}
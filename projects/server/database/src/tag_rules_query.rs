@@ -0,0 +1,81 @@
+use domain::tag_rules::{RuleSet, TagRule};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use uuid::Uuid;
+
+use crate::{
+    articles_query::{not_trashed, with_tags},
+    entities::{articles, tag_rules},
+    tags_query,
+};
+
+/// How many articles are loaded at once while reapplying the rules
+const REAPPLY_BATCH_SIZE: u64 = 200;
+
+/// All rules, oldest first.
+pub async fn all<C>(db: &C) -> Result<Vec<TagRule>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    tag_rules::Entity::find()
+        .order_by_asc(tag_rules::Column::CreatedAt)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(TagRule::try_from)
+        .collect()
+}
+
+pub async fn insert<C>(rule: TagRule, db: &C) -> Result<TagRule, DbErr>
+where
+    C: ConnectionTrait,
+{
+    tag_rules::Entity::insert(tag_rules::Model::from(rule.clone()).into_active_model())
+        .exec(db)
+        .await?;
+    Ok(rule)
+}
+
+pub async fn delete<C>(uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    tag_rules::Entity::delete_by_id(uuid).exec(db).await?;
+    Ok(())
+}
+
+/// Applies all rules to the articles that aren't in the trash and returns
+/// how many of them got new tags. Tags are only added, never removed. The
+/// articles are gone through in batches with only their tags, so large
+/// collections aren't loaded at once.
+pub async fn reapply<C>(db: &C) -> Result<usize, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let rules = RuleSet::new(all(db).await?);
+
+    let mut changed = 0;
+    let mut after = None;
+    loop {
+        let mut select = not_trashed()
+            .order_by_asc(articles::Column::Uuid)
+            .limit(REAPPLY_BATCH_SIZE);
+        if let Some(after) = after {
+            select = select.filter(articles::Column::Uuid.gt(after));
+        }
+        let models = select.all(db).await?;
+        let Some(last) = models.last() else {
+            return Ok(changed);
+        };
+        after = Some(last.uuid);
+
+        for mut article in with_tags(models, db).await? {
+            if rules.apply(&mut article) {
+                tags_query::set_for_article(article.uuid, article.tags, db).await?;
+                changed += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use domain::{articles::Article, tags::Tag};
+use sea_orm::{
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
+    sea_query::{Expr, OnConflict, Query},
+};
+use uuid::Uuid;
+
+use crate::{
+    articles_query::{not_trashed, with_details},
+    entities::{article_tags, articles, tags},
+};
+
+pub async fn all<C>(db: &C) -> Result<Vec<Tag>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    tags::Entity::find()
+        .order_by_asc(tags::Column::Name)
+        .all(db)
+        .await
+        .map(|tags| tags.into_iter().map(Into::into).collect())
+}
+
+pub async fn one_by_name<C>(db: &C, name: &str) -> Result<Option<Tag>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    tags::Entity::find()
+        .filter(tags::Column::Name.eq(name))
+        .one(db)
+        .await
+        .map(|opt_tag| opt_tag.map(Into::into))
+}
+
+pub async fn for_article<C>(db: &C, article_uuid: Uuid) -> Result<Vec<Tag>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    tags::Entity::find()
+        .inner_join(article_tags::Entity)
+        .filter(article_tags::Column::ArticleUuid.eq(article_uuid))
+        .order_by_asc(tags::Column::Name)
+        .all(db)
+        .await
+        .map(|tags| tags.into_iter().map(Into::into).collect())
+}
+
+/// All articles that are tagged with the tag of the given name, newest first.
+pub async fn articles_by_tag<C>(db: &C, name: &str) -> Result<Vec<Article>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let models = not_trashed()
+        .filter(articles::Column::Uuid.in_subquery(tagged_article_uuids(name)))
+        .order_by_desc(articles::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    with_details(models, db).await
+}
+
+/// Inserts the tags, tags whose name already exists are ignored. Returns the
+/// stored versions of all passed tags, so the uuids of already existing tags
+/// are the ones from the database.
+pub async fn insert_many<C>(tags: Vec<Tag>, db: &C) -> Result<Vec<Tag>, DbErr>
+where
+    C: ConnectionTrait,
+{
+    if tags.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let names = tags.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+    let entities = tags
+        .into_iter()
+        .map(|t| tags::Model::from(t).into_active_model());
+
+    tags::Entity::insert_many(entities)
+        .on_conflict(
+            OnConflict::column(tags::Column::Name)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(db)
+        .await?;
+
+    tags::Entity::find()
+        .filter(tags::Column::Name.is_in(names))
+        .all(db)
+        .await
+        .map(|tags| tags.into_iter().map(Into::into).collect())
+}
+
+pub async fn rename<C>(tag_uuid: Uuid, name: String, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    tags::Entity::update(tags::ActiveModel {
+        uuid: Set(tag_uuid),
+        name: Set(name),
+    })
+    .exec(db)
+    .await
+    .map(|_| ())
+}
+
+pub async fn delete<C>(tag_uuid: Uuid, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    tags::Entity::delete_by_id(tag_uuid)
+        .exec(db)
+        .await
+        .map(|_| ())
+}
+
+/// Replaces all tags of the article with the passed ones, creating the
+/// tags that dont exist yet.
+pub async fn set_for_article<C>(article_uuid: Uuid, tags: Vec<Tag>, db: &C) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    article_tags::Entity::delete_many()
+        .filter(article_tags::Column::ArticleUuid.eq(article_uuid))
+        .exec(db)
+        .await?;
+
+    let joins = insert_many(tags, db)
+        .await?
+        .into_iter()
+        .map(|tag| {
+            article_tags::Model {
+                article_uuid,
+                tag_uuid: tag.uuid,
+            }
+            .into_active_model()
+        })
+        .collect::<Vec<_>>();
+
+    if joins.is_empty() {
+        return Ok(());
+    }
+
+    article_tags::Entity::insert_many(joins)
+        .exec(db)
+        .await
+        .map(|_| ())
+}
+
+pub(crate) fn tagged_article_uuids(name: &str) -> sea_orm::sea_query::SelectStatement {
+    Query::select()
+        .column((article_tags::Entity, article_tags::Column::ArticleUuid))
+        .from(article_tags::Entity)
+        .inner_join(
+            tags::Entity,
+            Expr::col((tags::Entity, tags::Column::Uuid))
+                .equals((article_tags::Entity, article_tags::Column::TagUuid)),
+        )
+        .and_where(Expr::col((tags::Entity, tags::Column::Name)).eq(name))
+        .to_owned()
+}
@@ -0,0 +1,159 @@
+//! Pages name the urls of their stylesheets, images and redirects
+//! themselves. Without a check they could make the server fetch from itself
+//! or its network, e.g. `http://169.254.169.254`, and the snapshot would
+//! publish the response. Only public addresses are fetched from: hosts are
+//! checked after they are resolved and addresses in urls on every redirect.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use url::{Host, Url};
+
+/// The host only has loopback, link-local or private addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateAddress {
+    pub host: String,
+}
+
+impl Display for PrivateAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a public address", self.host)
+    }
+}
+
+impl Error for PrivateAddress {}
+
+/// Fails for urls whose host is a non public ip address, hostnames are
+/// checked by [`PublicResolver`].
+pub(crate) fn check_url(url: &Url) -> Result<(), PrivateAddress> {
+    let ip = match url.host() {
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(Host::Domain(_)) | None => return Ok(()),
+    };
+    match is_public(ip) {
+        true => Ok(()),
+        false => Err(PrivateAddress {
+            host: ip.to_string(),
+        }),
+    }
+}
+
+/// Resolves hosts like the system does, but only to their public addresses.
+#[derive(Debug)]
+pub(crate) struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addresses = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|address| is_public(address.ip()))
+                .collect::<Vec<_>>();
+            if addresses.is_empty() {
+                return Err(PrivateAddress { host }.into());
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+pub(crate) fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    // `0.0.0.0/8` is "this network", `100.64.0.0/10` the shared address
+    // space of carrier-grade NATs, `198.18.0.0/15` for benchmarking and
+    // `240.0.0.0/4` reserved
+    let is_special = first == 0
+        || (first == 100 && (second & 0xc0) == 64)
+        || (first == 198 && (second & 0xfe) == 18)
+        || first >= 240;
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || is_special)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local())
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::addresses::{PrivateAddress, check_url, is_public};
+
+    #[test]
+    fn private_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.178.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "198.18.0.1",
+            "198.19.255.254",
+            "240.0.0.1",
+            "255.255.255.255",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn other_addresses_are_public() {
+        for ip in [
+            "93.184.215.14",
+            "1.1.1.1",
+            "100.128.0.1",
+            "198.20.0.1",
+            "223.255.255.1",
+            "2606:4700::1111",
+        ] {
+            assert!(is_public(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn check_url_refuses_private_ips() {
+        let url = Url::parse("http://169.254.169.254/latest/meta-data").unwrap();
+        assert_eq!(
+            Err(PrivateAddress {
+                host: String::from("169.254.169.254")
+            }),
+            check_url(&url)
+        );
+        assert_eq!(
+            Ok(()),
+            check_url(&Url::parse("https://example.com").unwrap())
+        );
+    }
+}
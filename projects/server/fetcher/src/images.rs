@@ -0,0 +1,234 @@
+//! Favicons and preview images of articles. Images are downloaded once and
+//! stored scaled down, so a page can't fill the disk with huge images.
+
+use std::{error::Error, fmt::Display, io::Cursor};
+
+use domain::images::StoredImage;
+use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::{Fetch, FetchError, metadata::fetch_metadata};
+
+/// Larger downloads aren't decoded at all
+pub const MAX_IMAGE_BYTES: usize = 2 * 1024 * 1024;
+/// Images claiming to be wider or higher aren't decoded, which also stops
+/// small files that decompress into huge images
+const MAX_DECODED_DIMENSION: u32 = 8192;
+const MAX_DECODE_ALLOCATION: u64 = 256 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageKind {
+    Favicon,
+    Preview,
+}
+
+impl ImageKind {
+    /// Images are scaled down to fit into a square of this size
+    pub fn max_size(&self) -> u32 {
+        match self {
+            ImageKind::Favicon => 64,
+            ImageKind::Preview => 600,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ImageError {
+    Fetch(FetchError),
+    TooLarge { limit: usize },
+    Decode(image::ImageError),
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Fetch(err) => write!(f, "{err}"),
+            ImageError::TooLarge { limit } => write!(f, "image is larger than {limit} bytes"),
+            ImageError::Decode(err) => write!(f, "image can't be read: {err}"),
+        }
+    }
+}
+
+impl Error for ImageError {}
+
+impl From<FetchError> for ImageError {
+    fn from(value: FetchError) -> Self {
+        ImageError::Fetch(value)
+    }
+}
+
+impl From<image::ImageError> for ImageError {
+    fn from(value: image::ImageError) -> Self {
+        ImageError::Decode(value)
+    }
+}
+
+/// The images of an article, `None` where the page has none or it couldn't
+/// be used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArticleImages {
+    pub favicon: Option<StoredImage>,
+    pub preview: Option<StoredImage>,
+}
+
+/// Downloads the favicon of the site and the preview image of the page, the
+/// favicon falls back to `/favicon.ico`. Missing or broken images are left
+/// out, only a page that can't be downloaded is an error.
+pub async fn article_images<F>(fetcher: &F, url: &str) -> Result<ArticleImages, FetchError>
+where
+    F: Fetch,
+{
+    let metadata = fetch_metadata(fetcher, url).await?;
+    let icon = metadata.icon.or_else(|| {
+        let fallback = Url::parse(url).ok()?.join("/favicon.ico").ok()?;
+        Some(String::from(fallback))
+    });
+
+    let mut images = ArticleImages::default();
+    if let Some(icon) = icon {
+        images.favicon = fetch_image(fetcher, &icon, ImageKind::Favicon).await.ok();
+    }
+    if let Some(image) = metadata.image {
+        images.preview = fetch_image(fetcher, &image, ImageKind::Preview).await.ok();
+    }
+    Ok(images)
+}
+
+pub async fn fetch_image<F>(
+    fetcher: &F,
+    url: &str,
+    kind: ImageKind,
+) -> Result<StoredImage, ImageError>
+where
+    F: Fetch,
+{
+    let page = fetcher.get(url).await?;
+    shrink(&page.body, kind)
+}
+
+/// Decodes the image and scales it down to the size of the kind. Favicons
+/// are stored as png to keep their transparency, preview images as jpeg.
+pub fn shrink(data: &[u8], kind: ImageKind) -> Result<StoredImage, ImageError> {
+    if data.len() > MAX_IMAGE_BYTES {
+        return Err(ImageError::TooLarge {
+            limit: MAX_IMAGE_BYTES,
+        });
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODED_DIMENSION);
+    limits.max_image_height = Some(MAX_DECODED_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOCATION);
+
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(image::ImageError::IoError)?;
+    reader.limits(limits);
+    let image = reader.decode()?;
+
+    let size = kind.max_size();
+    let image = match image.width() > size || image.height() > size {
+        true => image.thumbnail(size, size),
+        false => image,
+    };
+
+    let mut data = vec![];
+    let content_type = match kind {
+        ImageKind::Favicon => {
+            image.write_to(&mut Cursor::new(&mut data), ImageFormat::Png)?;
+            "image/png"
+        }
+        ImageKind::Preview => {
+            DynamicImage::from(image.to_rgb8())
+                .write_to(&mut Cursor::new(&mut data), ImageFormat::Jpeg)?;
+            "image/jpeg"
+        }
+    };
+
+    Ok(StoredImage {
+        hash: format!("{:x}", Sha256::digest(&data)),
+        content_type: content_type.to_string(),
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use domain::images::is_image_hash;
+    use image::{ImageFormat, RgbaImage};
+
+    use crate::{
+        images::{ImageError, ImageKind, MAX_IMAGE_BYTES, article_images, shrink},
+        test_server::{local_fetcher, ok, ok_bytes, serve},
+    };
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = vec![];
+        RgbaImage::new(width, height)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    fn dimensions(data: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory(data).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn large_images_are_scaled_down() {
+        let preview = shrink(&png(1200, 630), ImageKind::Preview).unwrap();
+        assert_eq!("image/jpeg", preview.content_type);
+        assert_eq!((600, 315), dimensions(&preview.data));
+        assert!(is_image_hash(&preview.hash));
+
+        let favicon = shrink(&png(180, 180), ImageKind::Favicon).unwrap();
+        assert_eq!("image/png", favicon.content_type);
+        assert_eq!((64, 64), dimensions(&favicon.data));
+    }
+
+    #[test]
+    fn small_images_keep_their_size() {
+        let favicon = shrink(&png(16, 16), ImageKind::Favicon).unwrap();
+        assert_eq!((16, 16), dimensions(&favicon.data));
+        assert_eq!(favicon, shrink(&png(16, 16), ImageKind::Favicon).unwrap());
+    }
+
+    #[test]
+    fn broken_and_huge_images_are_rejected() {
+        let broken = shrink(b"<svg></svg>", ImageKind::Favicon);
+        assert!(matches!(broken, Err(ImageError::Decode(_))));
+
+        let huge = shrink(&vec![0; MAX_IMAGE_BYTES + 1], ImageKind::Preview);
+        assert!(matches!(huge, Err(ImageError::TooLarge { .. })));
+    }
+
+    #[tokio::test]
+    async fn downloads_favicon_and_preview_image() {
+        let images = serve(ok_bytes("image/png", &png(32, 32))).await;
+        let page = format!(
+            r#"<html><head>
+            <link rel="icon" href="{images}/icon.png">
+            <meta property="og:image" content="{images}/preview.png">
+            </head></html>"#
+        );
+        let url = serve(ok("text/html", &page)).await;
+
+        let found = article_images(&local_fetcher(), &url).await.unwrap();
+        assert_eq!("image/png", found.favicon.unwrap().content_type);
+        assert_eq!("image/jpeg", found.preview.unwrap().content_type);
+    }
+
+    #[tokio::test]
+    async fn missing_images_are_left_out() {
+        // the fallback /favicon.ico is the html page itself
+        let url = serve(ok("text/html", "<html><head></head></html>")).await;
+
+        let found = article_images(&local_fetcher(), &url).await.unwrap();
+        assert_eq!(None, found.favicon);
+        assert_eq!(None, found.preview);
+    }
+}
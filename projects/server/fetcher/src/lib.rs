@@ -0,0 +1,414 @@
+//! Downloads of other sites. Everything that fetches takes a [`Fetch`] so
+//! it can be tested against a local server instead of the internet.
+
+use std::{error::Error, fmt::Display, sync::Arc, time::Duration};
+
+use reqwest::{Client, Url, header::CONTENT_TYPE, redirect::Policy};
+
+use crate::addresses::{PrivateAddress, PublicResolver, check_url};
+
+pub mod addresses;
+pub mod images;
+pub mod link_health;
+pub mod metadata;
+pub mod reader;
+pub mod resolver;
+pub mod snapshot;
+pub mod subscriptions;
+
+pub trait Fetch: Send + Sync {
+    /// Downloads the url, following redirects. Responses with an error
+    /// status are errors.
+    fn get(&self, url: &str) -> impl Future<Output = Result<Page, FetchError>> + Send;
+
+    /// Requests only the status of the url, following redirects. Error
+    /// statuses are part of the result, only an unreachable site is an
+    /// error.
+    fn status(&self, url: &str) -> impl Future<Output = Result<Status, FetchError>> + Send;
+}
+
+/// A downloaded response
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page {
+    /// the url the page was downloaded from after all redirects
+    pub url: String,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl Page {
+    /// Pages without a content type are assumed to be html.
+    pub fn is_html(&self) -> bool {
+        self.content_type
+            .as_ref()
+            .is_none_or(|content_type| content_type.contains("html"))
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// The status of a url
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Status {
+    /// the url that responded after all redirects
+    pub url: String,
+    pub code: u16,
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Request(reqwest::Error),
+    Status(u16),
+    /// The body is larger than the limit of the fetcher
+    TooLarge {
+        limit: usize,
+    },
+    /// The url or a redirect leads to an address that isn't public
+    PrivateAddress(PrivateAddress),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Request(err) if err.is_timeout() => write!(f, "request timed out"),
+            FetchError::Request(err) if err.is_redirect() => write!(f, "too many redirects"),
+            FetchError::Request(err) => write!(f, "request failed: {err}"),
+            FetchError::Status(status) => write!(f, "responded with status {status}"),
+            FetchError::TooLarge { limit } => write!(f, "response is larger than {limit} bytes"),
+            FetchError::PrivateAddress(err) => write!(f, "refused to fetch: {err}"),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Refused addresses are found in the sources of the error, they are
+/// reported by the resolver or the redirect policy.
+impl From<reqwest::Error> for FetchError {
+    fn from(value: reqwest::Error) -> Self {
+        let mut source = value.source();
+        while let Some(err) = source {
+            if let Some(private) = err.downcast_ref::<PrivateAddress>() {
+                return FetchError::PrivateAddress(private.clone());
+            }
+            source = err.source();
+        }
+        FetchError::Request(value)
+    }
+}
+
+impl From<PrivateAddress> for FetchError {
+    fn from(value: PrivateAddress) -> Self {
+        FetchError::PrivateAddress(value)
+    }
+}
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+/// Chains of more redirects are errors, they are most likely loops
+pub const MAX_REDIRECTS: usize = 10;
+
+const USER_AGENT: &str = concat!("articles_collect/", env!("CARGO_PKG_VERSION"));
+
+/// [`Fetch`] over http and https. Every request has to be done within the
+/// timeout, follows at most [`MAX_REDIRECTS`] redirects and bodies are only
+/// read up to the limit, so a slow or huge page can't hold up the server.
+/// Only public addresses are fetched from, see [`addresses`].
+#[derive(Clone, Debug)]
+pub struct HttpFetcher {
+    client: Client,
+    max_body_bytes: usize,
+    allow_private_addresses: bool,
+}
+
+impl HttpFetcher {
+    pub fn new(timeout: Duration, max_body_bytes: usize) -> Result<Self, FetchError> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .redirect(Policy::custom(|attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    return attempt.error("too many redirects");
+                }
+                match check_url(attempt.url()) {
+                    Ok(()) => attempt.follow(),
+                    Err(err) => attempt.error(err),
+                }
+            }))
+            .dns_resolver(Arc::new(PublicResolver))
+            .user_agent(USER_AGENT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            max_body_bytes,
+            allow_private_addresses: false,
+        })
+    }
+
+    pub fn with_defaults() -> Result<Self, FetchError> {
+        Self::new(DEFAULT_TIMEOUT, DEFAULT_MAX_BODY_BYTES)
+    }
+
+    /// Like [`HttpFetcher::new`] but fetches from loopback, link-local and
+    /// private addresses as well. Only meant for local servers, like the
+    /// ones standing in for other sites in tests.
+    pub fn allowing_private_addresses(
+        timeout: Duration,
+        max_body_bytes: usize,
+    ) -> Result<Self, FetchError> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .redirect(Policy::limited(MAX_REDIRECTS))
+            .user_agent(USER_AGENT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            max_body_bytes,
+            allow_private_addresses: true,
+        })
+    }
+
+    /// Fails for urls with a private ip address as host, urls that can't be
+    /// parsed are left to the client to report.
+    fn check(&self, url: &str) -> Result<(), FetchError> {
+        if self.allow_private_addresses {
+            return Ok(());
+        }
+        match Url::parse(url) {
+            Ok(url) => Ok(check_url(&url)?),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl Fetch for HttpFetcher {
+    async fn get(&self, url: &str) -> Result<Page, FetchError> {
+        self.check(url)?;
+        let mut response = self.client.get(url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(FetchError::Status(response.status().as_u16()));
+        }
+
+        let limit = self.max_body_bytes;
+        if response
+            .content_length()
+            .is_some_and(|len| len as usize > limit)
+        {
+            return Err(FetchError::TooLarge { limit });
+        }
+
+        let url = response.url().to_string();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+
+        // the content length can be missing or wrong, so the limit is also
+        // checked while reading
+        let mut body = vec![];
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(FetchError::TooLarge { limit });
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(Page {
+            url,
+            content_type,
+            body,
+        })
+    }
+
+    async fn status(&self, url: &str) -> Result<Status, FetchError> {
+        self.check(url)?;
+        let mut response = self.client.head(url).send().await?;
+        // some servers answer HEAD requests with an error while the page
+        // works, so the page is requested without reading its body
+        if !response.status().is_success() {
+            response = self.client.get(url).send().await?;
+        }
+
+        Ok(Status {
+            url: response.url().to_string(),
+            code: response.status().as_u16(),
+        })
+    }
+}
+
+/// A local http server standing in for other sites in tests.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::{DEFAULT_MAX_BODY_BYTES, DEFAULT_TIMEOUT, HttpFetcher};
+
+    /// A fetcher with the default limits that can reach the local servers.
+    pub fn local_fetcher() -> HttpFetcher {
+        HttpFetcher::allowing_private_addresses(DEFAULT_TIMEOUT, DEFAULT_MAX_BODY_BYTES).unwrap()
+    }
+
+    /// Answers every request with the raw http `response` and returns the
+    /// base url of the server.
+    pub async fn serve(response: impl Into<Vec<u8>>) -> String {
+        let response = response.into();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = vec![0; 4096];
+                let _ = socket.read(&mut request).await;
+                let _ = socket.write_all(&response).await;
+            }
+        });
+
+        format!("http://{address}")
+    }
+
+    pub fn ok(content_type: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\n\
+            connection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    /// Like [`ok`] for bodies that aren't text.
+    pub fn ok_bytes(content_type: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\n\
+            connection: close\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        DEFAULT_MAX_BODY_BYTES, DEFAULT_TIMEOUT, Fetch, FetchError, HttpFetcher,
+        addresses::PrivateAddress,
+        test_server::{local_fetcher, ok, serve},
+    };
+
+    #[tokio::test]
+    async fn fetches_body_and_content_type() {
+        let url = serve(ok("text/html", "<title>Hi</title>")).await;
+        let page = local_fetcher().get(&format!("{url}/page")).await.unwrap();
+
+        assert_eq!(format!("{url}/page"), page.url);
+        assert_eq!(Some(String::from("text/html")), page.content_type);
+        assert_eq!("<title>Hi</title>", page.text());
+    }
+
+    #[tokio::test]
+    async fn error_status_is_an_error() {
+        let url = serve(String::from(
+            "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ))
+        .await;
+        let result = local_fetcher().get(&url).await;
+        assert!(matches!(result, Err(FetchError::Status(404))));
+    }
+
+    #[tokio::test]
+    async fn error_status_is_part_of_the_status() {
+        let url = serve(String::from(
+            "HTTP/1.1 410 Gone\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ))
+        .await;
+        let status = local_fetcher()
+            .status(&format!("{url}/page"))
+            .await
+            .unwrap();
+        assert_eq!(410, status.code);
+        assert_eq!(format!("{url}/page"), status.url);
+    }
+
+    #[tokio::test]
+    async fn body_over_the_limit_is_an_error() {
+        let url = serve(ok("text/plain", &"a".repeat(100))).await;
+        let result = HttpFetcher::allowing_private_addresses(Duration::from_secs(5), 50)
+            .unwrap()
+            .get(&url)
+            .await;
+        assert!(matches!(result, Err(FetchError::TooLarge { limit: 50 })));
+    }
+
+    #[tokio::test]
+    async fn body_without_length_is_limited_while_reading() {
+        let body = "a".repeat(100);
+        let url = serve(format!(
+            "HTTP/1.1 200 OK\r\nconnection: close\r\n\r\n{body}"
+        ))
+        .await;
+        let result = HttpFetcher::allowing_private_addresses(Duration::from_secs(5), 50)
+            .unwrap()
+            .get(&url)
+            .await;
+        assert!(matches!(result, Err(FetchError::TooLarge { limit: 50 })));
+    }
+
+    #[tokio::test]
+    async fn redirect_loops_are_errors() {
+        let url = serve(String::from(
+            "HTTP/1.1 302 Found\r\nlocation: /again\r\ncontent-length: 0\r\n\
+            connection: close\r\n\r\n",
+        ))
+        .await;
+
+        let error = local_fetcher().get(&url).await.unwrap_err();
+        assert_eq!("too many redirects", error.to_string());
+    }
+
+    #[tokio::test]
+    async fn slow_responses_time_out() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        // connections are queued by the os but never answered
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let error = HttpFetcher::allowing_private_addresses(Duration::from_millis(100), 50)
+            .unwrap()
+            .get(&url)
+            .await
+            .unwrap_err();
+        assert_eq!("request timed out", error.to_string());
+    }
+
+    #[tokio::test]
+    async fn private_addresses_are_refused() {
+        let url = serve(ok("text/plain", "secret")).await;
+        let port = url.rsplit(':').next().unwrap();
+        let fetcher = HttpFetcher::new(DEFAULT_TIMEOUT, DEFAULT_MAX_BODY_BYTES).unwrap();
+
+        let by_ip = fetcher.get(&url).await.unwrap_err();
+        assert!(matches!(
+            by_ip,
+            FetchError::PrivateAddress(PrivateAddress { host }) if host == "127.0.0.1"
+        ));
+
+        let by_name = fetcher
+            .status(&format!("http://localhost:{port}"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            by_name,
+            FetchError::PrivateAddress(PrivateAddress { host }) if host == "localhost"
+        ));
+    }
+}
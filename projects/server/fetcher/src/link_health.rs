@@ -0,0 +1,140 @@
+//! Checks whether the links of articles still work, see
+//! [`domain::link_health`].
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use domain::link_health::LinkCheck;
+use futures::{StreamExt, stream};
+use url::Url;
+
+use crate::Fetch;
+
+/// How many links of the same site are checked at once, so a site with many
+/// collected articles isn't flooded with requests
+const CONCURRENT_CHECKS_PER_DOMAIN: usize = 2;
+const CONCURRENT_DOMAINS: usize = 8;
+
+/// Checks the url. A site that can't be reached is a check without status.
+pub async fn check_link<F>(fetcher: &F, url: &str) -> LinkCheck
+where
+    F: Fetch,
+{
+    let (status, final_url) = match fetcher.status(url).await {
+        Ok(status) => {
+            let redirected = Url::parse(url).is_ok_and(|url| url.as_str() != status.url);
+            (Some(status.code), redirected.then_some(status.url))
+        }
+        Err(_) => (None, None),
+    };
+
+    LinkCheck {
+        url: url.to_string(),
+        status,
+        final_url,
+        checked_at: Utc::now(),
+    }
+}
+
+/// Checks all the links, each together with its key like the uuid of its
+/// article. Sites are checked concurrently while only a few links of each
+/// site are checked at once. The checks are returned in no particular order.
+pub async fn check_links<F, K>(fetcher: &F, links: Vec<(K, String)>) -> Vec<(K, LinkCheck)>
+where
+    F: Fetch,
+    K: Send,
+{
+    let mut by_domain = HashMap::<String, Vec<(K, String)>>::new();
+    for (key, url) in links {
+        let domain = Url::parse(&url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .unwrap_or_default();
+        by_domain.entry(domain).or_default().push((key, url));
+    }
+
+    stream::iter(by_domain.into_values())
+        .map(|links| {
+            stream::iter(links)
+                .map(|(key, url)| async move {
+                    let check = check_link(fetcher, &url).await;
+                    (key, check)
+                })
+                .buffer_unordered(CONCURRENT_CHECKS_PER_DOMAIN)
+                .collect::<Vec<_>>()
+        })
+        .buffer_unordered(CONCURRENT_DOMAINS)
+        .flat_map(stream::iter)
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::link_health::LinkHealth;
+
+    use crate::{
+        link_health::{check_link, check_links},
+        test_server::{local_fetcher, ok, serve},
+    };
+
+    #[tokio::test]
+    async fn working_links_are_healthy() {
+        let url = serve(ok("text/html", "<title>Hi</title>")).await;
+        let url = format!("{url}/post");
+
+        let check = check_link(&local_fetcher(), &url).await;
+        assert_eq!(Some(200), check.status);
+        assert_eq!(None, check.final_url);
+        assert_eq!(None, check.health(&url));
+    }
+
+    #[tokio::test]
+    async fn redirects_are_recorded() {
+        let target = serve(ok("text/html", "<title>Hi</title>")).await;
+        let url = serve(format!(
+            "HTTP/1.1 301 Moved Permanently\r\nlocation: {target}/new\r\n\
+            content-length: 0\r\nconnection: close\r\n\r\n"
+        ))
+        .await;
+
+        let check = check_link(&local_fetcher(), &url).await;
+        assert_eq!(Some(200), check.status);
+        assert_eq!(
+            Some(LinkHealth::Moved {
+                to: format!("{target}/new")
+            }),
+            check.health(&url)
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_pages_and_unreachable_sites_are_broken() {
+        let missing = serve(String::from(
+            "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ))
+        .await;
+        let unreachable = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+
+        let mut checks = check_links(
+            &local_fetcher(),
+            vec![(1, missing.clone()), (2, unreachable.clone())],
+        )
+        .await;
+        checks.sort_by_key(|(key, _)| *key);
+
+        assert_eq!(
+            vec![
+                (1, Some(LinkHealth::Broken { status: Some(404) })),
+                (2, Some(LinkHealth::Broken { status: None })),
+            ],
+            checks
+                .iter()
+                .map(|(key, check)| (*key, check.health(&check.url)))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Titles and descriptions of collected articles, read from the linked
+//! pages themselves.
+
+use domain::{articles::Article, paste::get_title_from_url};
+use formats::metadata::{self, PageMetadata};
+use url::Url;
+
+use crate::{Fetch, FetchError};
+
+/// Downloads the page and reads its metadata, the canonical url and the
+/// urls of the images are made absolute. Pages that aren't html, like pdfs
+/// or images, have none.
+pub async fn fetch_metadata<F>(fetcher: &F, url: &str) -> Result<PageMetadata, FetchError>
+where
+    F: Fetch,
+{
+    let page = fetcher.get(url).await?;
+    if !page.is_html() {
+        return Ok(PageMetadata::default());
+    }
+
+    let mut metadata = metadata::parse(&page.text());
+    let absolute = |url: Option<String>| {
+        let absolute = Url::parse(&page.url).ok()?.join(&url?).ok()?;
+        Some(String::from(absolute))
+    };
+    metadata.canonical_url = absolute(metadata.canonical_url);
+    metadata.image = absolute(metadata.image);
+    metadata.icon = absolute(metadata.icon);
+    Ok(metadata)
+}
+
+/// Fills the article with the metadata of its page. Only a title that was
+/// built from the url is replaced and the description only if the article
+/// has none. The canonical url replaces the url of the article as long as
+/// it points to the same site, the replaced url is kept as the original url.
+pub async fn enrich<F>(fetcher: &F, mut article: Article) -> Result<Article, FetchError>
+where
+    F: Fetch,
+{
+    let metadata = fetch_metadata(fetcher, &article.url).await?;
+
+    if let Some(title) = metadata.title
+        && article.title == get_title_from_url(article.url.clone())
+    {
+        article.title = title;
+    }
+    if article.description.is_none() {
+        article.description = metadata.description;
+    }
+    if let Some(canonical) = metadata.canonical_url
+        && is_same_site(&canonical, &article.url)
+        && canonical != article.url
+    {
+        let url = std::mem::replace(&mut article.url, canonical);
+        article.original_url.get_or_insert(url);
+    }
+    Ok(article)
+}
+
+fn is_same_site(url: &str, other: &str) -> bool {
+    match (site(url), site(other)) {
+        (Some(site), Some(other)) => site == other,
+        _ => false,
+    }
+}
+
+/// The host of the url without a leading `www.`
+fn site(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    Some(host.trim_start_matches("www.").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{articles::Article, paste::get_title_from_url};
+
+    use crate::{
+        FetchError,
+        metadata::enrich,
+        test_server::{local_fetcher, ok, serve},
+    };
+
+    const PAGE: &str = r#"<html><head>
+        <title>Modern LaTeX | GitHub</title>
+        <meta property="og:title" content="modern-latex">
+        <meta property="og:description" content="A short guide to LaTeX">
+        <link rel="canonical" href="/mrkline/modern-latex">
+        </head><body></body></html>"#;
+
+    fn article(url: String) -> Article {
+        Article::from_parts(get_title_from_url(url.clone()), url)
+    }
+
+    #[tokio::test]
+    async fn articles_get_the_metadata_of_their_page() {
+        let url = serve(ok("text/html; charset=utf-8", PAGE)).await;
+        let fetcher = local_fetcher();
+
+        let submitted = format!("{url}/mrkline/modern-latex?utm_source=x");
+        let article = enrich(&fetcher, article(submitted.clone())).await.unwrap();
+        assert_eq!("modern-latex", article.title);
+        assert_eq!(
+            Some(String::from("A short guide to LaTeX")),
+            article.description
+        );
+        assert_eq!(format!("{url}/mrkline/modern-latex"), article.url);
+        assert_eq!(Some(submitted), article.original_url);
+    }
+
+    #[tokio::test]
+    async fn given_titles_and_descriptions_are_kept() {
+        let url = serve(ok("text/html", PAGE)).await;
+        let mut given = Article::from_parts(String::from("My title"), format!("{url}/post"));
+        given.description = Some(String::from("My description"));
+
+        let article = enrich(&local_fetcher(), given).await.unwrap();
+        assert_eq!("My title", article.title);
+        assert_eq!(Some(String::from("My description")), article.description);
+    }
+
+    #[tokio::test]
+    async fn canonical_urls_of_other_sites_are_ignored() {
+        let page = PAGE.replace(
+            "/mrkline/modern-latex",
+            "https://elsewhere.example.com/post",
+        );
+        let url = serve(ok("text/html", &page)).await;
+
+        let article = enrich(&local_fetcher(), article(format!("{url}/post")))
+            .await
+            .unwrap();
+        assert_eq!(format!("{url}/post"), article.url);
+        assert_eq!(None, article.original_url);
+    }
+
+    #[tokio::test]
+    async fn other_content_is_left_alone() {
+        let url = serve(ok("application/pdf", "%PDF-1.7")).await;
+        let pdf = article(format!("{url}/paper.pdf"));
+
+        let article = enrich(&local_fetcher(), pdf.clone()).await.unwrap();
+        assert_eq!(pdf, article);
+    }
+
+    #[tokio::test]
+    async fn unreachable_pages_are_errors() {
+        let url = serve(String::from(
+            "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ))
+        .await;
+
+        let result = enrich(&local_fetcher(), article(format!("{url}/missing"))).await;
+        assert!(matches!(result, Err(FetchError::Status(404))));
+    }
+}
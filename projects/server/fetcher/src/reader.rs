@@ -0,0 +1,56 @@
+//! Readable content of collected articles for the reader view.
+
+use domain::reader::ArticleContent;
+use url::Url;
+
+use crate::{Fetch, FetchError};
+
+/// Downloads the page and extracts its article, see
+/// [`formats::reader::extract`]. Pages that aren't html or have no article
+/// have no content.
+pub async fn fetch_content<F>(fetcher: &F, url: &str) -> Result<Option<ArticleContent>, FetchError>
+where
+    F: Fetch,
+{
+    let page = fetcher.get(url).await?;
+    if !page.is_html() {
+        return Ok(None);
+    }
+    let Ok(base_url) = Url::parse(&page.url) else {
+        return Ok(None);
+    };
+    Ok(formats::reader::extract(&page.text(), &base_url))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        reader::fetch_content,
+        test_server::{local_fetcher, ok, serve},
+    };
+
+    #[tokio::test]
+    async fn links_are_made_absolute_against_the_page() {
+        let paragraph = "<p>A paragraph that is long enough, with some commas, to count as \
+            the text of an article, which <a href=\"/other\">links elsewhere</a>.</p>";
+        let page = format!(
+            "<html><body><article>{}</article></body></html>",
+            paragraph.repeat(3)
+        );
+        let url = serve(ok("text/html", &page)).await;
+
+        let content = fetch_content(&local_fetcher(), &format!("{url}/post"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(content.html.contains(&format!("<a href=\"{url}/other\">")));
+    }
+
+    #[tokio::test]
+    async fn other_content_has_no_article() {
+        let url = serve(ok("application/pdf", "%PDF-1.7")).await;
+
+        let content = fetch_content(&local_fetcher(), &url).await.unwrap();
+        assert_eq!(None, content);
+    }
+}
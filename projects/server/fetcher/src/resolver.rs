@@ -0,0 +1,168 @@
+//! Resolves short links and AMP pages to the article they lead to, see
+//! [`domain::urls::needs_resolving`].
+
+use domain::{
+    articles::Article,
+    paste::get_title_from_url,
+    urls::{is_amp, normalize_url},
+};
+use formats::metadata;
+use url::Url;
+
+use crate::{Fetch, FetchError};
+
+/// Follows the redirects of the url and then the canonical link of the page
+/// it ended on if that is an AMP page. The canonical link of a page isn't
+/// followed any further. Only AMP pages are downloaded, the redirects of
+/// other urls are followed without their bodies.
+pub async fn resolve<F>(fetcher: &F, url: &str) -> Result<String, FetchError>
+where
+    F: Fetch,
+{
+    let mut url = url.to_string();
+    if !is_amp(&url) {
+        let status = fetcher.status(&url).await?;
+        if status.code >= 400 {
+            return Err(FetchError::Status(status.code));
+        }
+        if !is_amp(&status.url) {
+            return Ok(status.url);
+        }
+        url = status.url;
+    }
+
+    let page = fetcher.get(&url).await?;
+    if !page.is_html() {
+        return Ok(page.url);
+    }
+
+    let canonical = metadata::parse(&page.text())
+        .canonical_url
+        .and_then(|canonical| Url::parse(&page.url).ok()?.join(&canonical).ok())
+        .map(String::from)
+        .filter(|canonical| normalize_url(canonical).is_ok());
+    Ok(canonical.unwrap_or(page.url))
+}
+
+/// Replaces the url of the article by the url it resolves to and keeps the
+/// submitted url as the original url. A title that was built from the
+/// submitted url is built from the resolved one instead. The article is
+/// kept as it is if its url can't be resolved.
+pub async fn resolve_article<F>(fetcher: &F, mut article: Article) -> Article
+where
+    F: Fetch,
+{
+    let resolved = match resolve(fetcher, &article.url).await {
+        Ok(resolved) if resolved != article.url => resolved,
+        _ => return article,
+    };
+
+    if article.title == get_title_from_url(article.url.clone()) {
+        article.title = get_title_from_url(resolved.clone());
+    }
+    let original = std::mem::replace(&mut article.url, resolved);
+    article.original_url = Some(original);
+    article
+}
+
+#[cfg(test)]
+mod tests {
+    use domain::{articles::Article, paste::get_title_from_url};
+
+    use crate::{
+        resolver::{resolve, resolve_article},
+        test_server::{local_fetcher, ok, serve},
+    };
+
+    fn redirect(location: &str) -> String {
+        format!(
+            "HTTP/1.1 301 Moved Permanently\r\nlocation: {location}\r\n\
+            content-length: 0\r\nconnection: close\r\n\r\n"
+        )
+    }
+
+    #[tokio::test]
+    async fn redirects_are_followed() {
+        let article = serve(ok("text/html", "<title>Article</title>")).await;
+        let short = serve(redirect(&format!("{article}/post"))).await;
+        let shorter = serve(redirect(&format!("{short}/abc"))).await;
+
+        let resolved = resolve(&local_fetcher(), &shorter).await.unwrap();
+        assert_eq!(format!("{article}/post"), resolved);
+    }
+
+    #[tokio::test]
+    async fn canonical_links_are_honored() {
+        let amp = serve(ok(
+            "text/html",
+            r#"<html><head><link rel="canonical" href="https://example.com/news/story"></head></html>"#,
+        ))
+        .await;
+
+        let resolved = resolve(&local_fetcher(), &format!("{amp}/amp/news/story"))
+            .await
+            .unwrap();
+        assert_eq!("https://example.com/news/story", resolved);
+    }
+
+    #[tokio::test]
+    async fn canonical_links_that_arent_http_are_ignored() {
+        let page = serve(ok(
+            "text/html",
+            r#"<html><head><link rel="canonical" href="javascript:alert(1)"></head></html>"#,
+        ))
+        .await;
+
+        let resolved = resolve(&local_fetcher(), &format!("{page}/amp/story"))
+            .await
+            .unwrap();
+        assert_eq!(format!("{page}/amp/story"), resolved);
+    }
+
+    #[tokio::test]
+    async fn short_links_to_amp_pages_are_resolved_to_the_canonical_link() {
+        let amp = serve(ok(
+            "text/html",
+            r#"<html><head><link rel="canonical" href="https://example.com/news/story"></head></html>"#,
+        ))
+        .await;
+        let short = serve(redirect(&format!("{amp}/amp/news/story"))).await;
+
+        let resolved = resolve(&local_fetcher(), &short).await.unwrap();
+        assert_eq!("https://example.com/news/story", resolved);
+    }
+
+    #[tokio::test]
+    async fn canonical_links_of_other_pages_are_ignored() {
+        let page = serve(ok(
+            "text/html",
+            r#"<html><head><link rel="canonical" href="https://example.com/news/story"></head></html>"#,
+        ))
+        .await;
+        let short = serve(redirect(&format!("{page}/news/story"))).await;
+
+        let resolved = resolve(&local_fetcher(), &short).await.unwrap();
+        assert_eq!(format!("{page}/news/story"), resolved);
+    }
+
+    #[tokio::test]
+    async fn resolved_articles_keep_their_original_url() {
+        let target = serve(ok("text/html", "<title>Article</title>")).await;
+        let short = serve(redirect(&format!("{target}/post"))).await;
+        let submitted = Article::from_parts(get_title_from_url(short.clone()), short.clone());
+
+        let article = resolve_article(&local_fetcher(), submitted).await;
+        assert_eq!(format!("{target}/post"), article.url);
+        assert_eq!(Some(short), article.original_url);
+        assert_eq!(get_title_from_url(format!("{target}/post")), article.title);
+    }
+
+    #[tokio::test]
+    async fn redirect_loops_keep_the_submitted_url() {
+        let looping = serve(redirect("/again")).await;
+        let submitted = Article::from_parts(String::from("Title"), looping.clone());
+
+        let article = resolve_article(&local_fetcher(), submitted.clone()).await;
+        assert_eq!(submitted, article);
+    }
+}
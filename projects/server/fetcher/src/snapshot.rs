@@ -0,0 +1,197 @@
+//! Archive snapshots of collected articles, see [`formats::snapshot`].
+
+use std::collections::HashSet;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::Utc;
+use domain::snapshots::Snapshot;
+use formats::snapshot::{self, Inlined};
+use futures::{StreamExt, stream};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::{Fetch, FetchError, Page};
+
+/// Further stylesheets and files of a page are left out
+const MAX_RESOURCES: usize = 100;
+/// All stylesheets and files of a page together, whatever doesn't fit
+/// anymore is left out
+const MAX_RESOURCE_BYTES: usize = 15 * 1024 * 1024;
+const CONCURRENT_DOWNLOADS: usize = 8;
+
+/// Downloads the page with its stylesheets, images and fonts and puts them
+/// into a single html file. Resources that can't be downloaded are left
+/// out, only a page that can't be downloaded is an error. Pages that aren't
+/// html have no snapshot.
+pub async fn take_snapshot<F>(fetcher: &F, url: &str) -> Result<Option<Snapshot>, FetchError>
+where
+    F: Fetch,
+{
+    let page = fetcher.get(url).await?;
+    if !page.is_html() {
+        return Ok(None);
+    }
+    let Ok(page_url) = Url::parse(&page.url) else {
+        return Ok(None);
+    };
+
+    let html = page.text();
+    let resources = snapshot::resources(&html, &page_url);
+    let mut budget = MAX_RESOURCE_BYTES;
+    let mut inlined = Inlined::default();
+
+    let mut files = resources.files;
+    for (url, stylesheet) in download(fetcher, resources.stylesheets, &mut budget).await {
+        let css = stylesheet.text();
+        if let Ok(css_url) = Url::parse(&url) {
+            files.extend(snapshot::css_urls(&css, &css_url));
+        }
+        inlined.stylesheets.insert(url, css);
+    }
+
+    let mut seen = HashSet::new();
+    files.retain(|url| seen.insert(url.clone()));
+    for (url, file) in download(fetcher, files, &mut budget).await {
+        inlined.files.insert(url, data_url(&file));
+    }
+
+    let html = snapshot::inline(&html, &page_url, &inlined);
+    Ok(Some(Snapshot {
+        hash: format!("{:x}", Sha256::digest(&html)),
+        html,
+        taken_at: Utc::now(),
+    }))
+}
+
+/// Downloads the urls together with the url they were requested as. Failed
+/// downloads are left out. The budget is charged as each download finishes,
+/// once a download doesn't fit anymore no further downloads are started.
+async fn download<F>(fetcher: &F, mut urls: Vec<String>, budget: &mut usize) -> Vec<(String, Page)>
+where
+    F: Fetch,
+{
+    urls.truncate(MAX_RESOURCES);
+    let mut downloads = stream::iter(urls)
+        .map(|url| async move {
+            let page = fetcher.get(&url).await;
+            (url, page)
+        })
+        .buffered(CONCURRENT_DOWNLOADS);
+
+    let mut downloaded = vec![];
+    while let Some((url, page)) = downloads.next().await {
+        let Ok(page) = page else {
+            continue;
+        };
+        let Some(left) = budget.checked_sub(page.body.len()) else {
+            break;
+        };
+        *budget = left;
+        downloaded.push((url, page));
+    }
+    downloaded
+}
+
+fn data_url(file: &Page) -> String {
+    let content_type = file
+        .content_type
+        .as_deref()
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim)
+        .filter(|content_type| {
+            content_type
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '+' | '.' | '-'))
+        })
+        .filter(|content_type| !content_type.is_empty())
+        .unwrap_or("application/octet-stream");
+    format!("data:{content_type};base64,{}", STANDARD.encode(&file.body))
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{Digest, Sha256};
+
+    use crate::{
+        snapshot::{download, take_snapshot},
+        test_server::{local_fetcher, ok, ok_bytes, serve},
+    };
+
+    #[tokio::test]
+    async fn inlines_stylesheets_and_images() {
+        let images = serve(ok_bytes("image/png; charset=binary", b"\x89PNG")).await;
+        let css = serve(ok(
+            "text/css",
+            &format!("body {{ background: url({images}/background.png) }}"),
+        ))
+        .await;
+        let page = format!(
+            r#"<html><head><link rel="stylesheet" href="{css}/site.css">
+            <script>alert(1)</script></head>
+            <body><img src="{images}/photo.png" alt="Photo"></body></html>"#
+        );
+        let url = serve(ok("text/html", &page)).await;
+
+        let snapshot = take_snapshot(&local_fetcher(), &url)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(snapshot.html.contains(
+            r#"<style>body { background: url("data:image/png;base64,iVBORw==") }</style>"#
+        ));
+        assert!(
+            snapshot
+                .html
+                .contains(r#"<img alt="Photo" src="data:image/png;base64,iVBORw==">"#)
+        );
+        assert!(!snapshot.html.contains("alert"));
+        assert_eq!(
+            format!("{:x}", Sha256::digest(&snapshot.html)),
+            snapshot.hash
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_resources_are_left_out() {
+        let missing = serve(String::from(
+            "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ))
+        .await;
+        let page =
+            format!(r#"<html><body><img src="{missing}/photo.png" alt="Photo"></body></html>"#);
+        let url = serve(ok("text/html", &page)).await;
+
+        let snapshot = take_snapshot(&local_fetcher(), &url)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(snapshot.html.contains(r#"<img alt="Photo">"#));
+    }
+
+    #[tokio::test]
+    async fn downloads_stop_when_the_budget_is_used_up() {
+        let mut urls = vec![];
+        for _ in 0..3 {
+            urls.push(serve(ok_bytes("image/png", b"123456")).await);
+        }
+
+        let mut budget = 10;
+        let downloaded = download(&local_fetcher(), urls.clone(), &mut budget).await;
+        assert_eq!(
+            vec![urls[0].clone()],
+            downloaded
+                .into_iter()
+                .map(|(url, _)| url)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(4, budget);
+    }
+
+    #[tokio::test]
+    async fn other_content_has_no_snapshot() {
+        let url = serve(ok("application/pdf", "%PDF-1.7")).await;
+
+        let snapshot = take_snapshot(&local_fetcher(), &url).await.unwrap();
+        assert_eq!(None, snapshot);
+    }
+}
@@ -0,0 +1,158 @@
+//! Polling of the feeds the collection is subscribed to.
+
+use std::{error::Error, fmt::Display};
+
+use domain::{
+    feeds::Feed,
+    submission::{LineOutcome, SubmissionEntry},
+};
+use formats::subscriptions::{self, FeedError};
+
+use crate::{Fetch, FetchError};
+
+/// The entries of a feed that are new since it was subscribed to, ready to
+/// be inserted like a submission.
+#[derive(Clone, Debug)]
+pub struct PolledFeed {
+    pub title: Option<String>,
+    pub entries: Vec<SubmissionEntry>,
+}
+
+#[derive(Debug)]
+pub enum PollError {
+    Fetch(FetchError),
+    Feed(FeedError),
+}
+
+impl Display for PollError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PollError::Fetch(err) => err.fmt(f),
+            PollError::Feed(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for PollError {}
+
+impl From<FetchError> for PollError {
+    fn from(value: FetchError) -> Self {
+        PollError::Fetch(value)
+    }
+}
+
+impl From<FeedError> for PollError {
+    fn from(value: FeedError) -> Self {
+        PollError::Feed(value)
+    }
+}
+
+/// Downloads and parses the feed. Entries published before the feed was
+/// subscribed to are skipped, so subscribing doesn't flood the collection
+/// with old posts. Entries without a date are always kept, the duplicate
+/// check on insert skips them if they were already collected.
+pub async fn poll<F>(fetcher: &F, feed: &Feed) -> Result<PolledFeed, PollError>
+where
+    F: Fetch,
+{
+    let page = fetcher.get(&feed.url).await?;
+    let contents = subscriptions::parse(&page.text())?;
+
+    let entries = contents
+        .entries
+        .into_iter()
+        .filter(|record| {
+            record
+                .added_at
+                .is_none_or(|added_at| added_at >= feed.created_at)
+        })
+        .enumerate()
+        .map(|(index, mut record)| {
+            let url = record.url.clone();
+            record
+                .tags
+                .extend(feed.tags.iter().map(|tag| tag.name.clone()));
+            let article = record
+                .into_article()
+                .map(|mut article| {
+                    article.found_via = Some(feed.url.clone());
+                    article
+                })
+                .map_err(|invalid| LineOutcome::InvalidUrl(invalid.error.to_string()));
+            (index + 1, url, article)
+        })
+        .collect();
+
+    Ok(PolledFeed {
+        title: contents.title,
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use domain::{feeds::Feed, tags::parse_tag_list};
+
+    use crate::{
+        subscriptions::{PollError, poll},
+        test_server::{local_fetcher, ok, serve},
+    };
+
+    const FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0">
+          <channel>
+            <title>Blog</title>
+            <item>
+              <title>New</title>
+              <link>https://blog.example.com/new</link>
+              <pubDate>Thu, 16 Nov 2023 00:00:00 +0000</pubDate>
+              <category>Rust</category>
+            </item>
+            <item>
+              <title>Undated</title>
+              <link>https://blog.example.com/undated</link>
+            </item>
+            <item>
+              <title>Old</title>
+              <link>https://blog.example.com/old</link>
+              <pubDate>Sun, 12 Nov 2023 00:00:00 +0000</pubDate>
+            </item>
+          </channel>
+        </rss>"#;
+
+    #[tokio::test]
+    async fn polls_entries_since_subscribing() {
+        let url = serve(ok("application/rss+xml", FEED)).await;
+        let mut feed = Feed::new(format!("{url}/feed.xml"), parse_tag_list("blogs, rust"));
+        feed.created_at = "2023-11-14T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let polled = poll(&local_fetcher(), &feed).await.unwrap();
+
+        assert_eq!(Some(String::from("Blog")), polled.title);
+        let articles = polled
+            .entries
+            .into_iter()
+            .map(|(_, _, article)| article.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(2, articles.len());
+        assert_eq!("New", articles[0].title);
+        assert_eq!("Undated", articles[1].title);
+        assert_eq!(Some(feed.url.clone()), articles[0].found_via);
+        let tags = articles[0]
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["rust", "blogs"], tags);
+    }
+
+    #[tokio::test]
+    async fn pages_that_are_no_feeds_are_errors() {
+        let url = serve(ok("text/html", "<html><body>Blog</body></html>")).await;
+        let feed = Feed::new(url, vec![]);
+
+        let result = poll(&local_fetcher(), &feed).await;
+        assert!(matches!(result, Err(PollError::Feed(_))));
+    }
+}
@@ -0,0 +1,188 @@
+//! Serializes articles for backups and for sharing them with others.
+
+use std::{error::Error, fmt::Display};
+
+use domain::articles::Article;
+use serde::Serialize;
+
+use crate::netscape;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    /// Netscape bookmark file that browsers can import
+    Html,
+}
+
+impl ExportFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            "html" | "htm" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Html => "html",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Html => "text/html; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Json(serde_json::Error),
+    Csv(csv::Error),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Json(err) => write!(f, "json export failed: {err}"),
+            ExportError::Csv(err) => write!(f, "csv export failed: {err}"),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+impl From<serde_json::Error> for ExportError {
+    fn from(value: serde_json::Error) -> Self {
+        ExportError::Json(value)
+    }
+}
+
+impl From<csv::Error> for ExportError {
+    fn from(value: csv::Error) -> Self {
+        ExportError::Csv(value)
+    }
+}
+
+pub fn export(articles: &[Article], format: ExportFormat) -> Result<String, ExportError> {
+    match format {
+        ExportFormat::Json => to_json(articles),
+        ExportFormat::Csv => to_csv(articles),
+        ExportFormat::Html => Ok(netscape::to_html(articles)),
+    }
+}
+
+/// The articles exactly like the api returns them.
+pub fn to_json(articles: &[Article]) -> Result<String, ExportError> {
+    Ok(serde_json::to_string_pretty(articles)?)
+}
+
+/// A row of the csv export, tags are joined into a single column.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    uuid: String,
+    title: &'a str,
+    url: &'a str,
+    description: Option<&'a str>,
+    found_via: Option<&'a str>,
+    tags: String,
+    created_at: String,
+    updated_at: String,
+    read_at: Option<String>,
+}
+
+impl<'a> From<&'a Article> for CsvRow<'a> {
+    fn from(article: &'a Article) -> Self {
+        Self {
+            uuid: article.uuid.to_string(),
+            title: &article.title,
+            url: &article.url,
+            description: article.description.as_deref(),
+            found_via: article.found_via.as_deref(),
+            tags: article
+                .tags
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            created_at: article.created_at.to_rfc3339(),
+            updated_at: article.updated_at.to_rfc3339(),
+            read_at: article.read_at.map(|r| r.to_rfc3339()),
+        }
+    }
+}
+
+pub fn to_csv(articles: &[Article]) -> Result<String, ExportError> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for article in articles {
+        writer.serialize(CsvRow::from(article))?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| csv::Error::from(err.into_error()))?;
+    Ok(String::from_utf8(bytes).expect("csv writer only writes the passed utf-8 strings"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use domain::{articles::Article, tags::parse_tag_list};
+
+    use crate::export::{ExportFormat, to_csv, to_json};
+
+    fn article() -> Article {
+        let mut article = Article::from_parts(
+            String::from("Commas, \"quotes\" and more"),
+            String::from("https://example.com/article"),
+        )
+        .with_tags(parse_tag_list("rust, databases"));
+        article.created_at = DateTime::from_timestamp(1700000000, 0).unwrap();
+        article.updated_at = article.created_at;
+        article
+    }
+
+    #[test]
+    fn to_json_round_trips() {
+        let articles = vec![article()];
+        let json = to_json(&articles).unwrap();
+        assert_eq!(
+            articles,
+            serde_json::from_str::<Vec<Article>>(&json).unwrap()
+        );
+    }
+
+    #[test]
+    fn to_csv_quotes_fields() {
+        let article = article();
+        let csv = to_csv(std::slice::from_ref(&article)).unwrap();
+        let expected = format!(
+            "uuid,title,url,description,found_via,tags,created_at,updated_at,read_at\n\
+            {},\"Commas, \"\"quotes\"\" and more\",https://example.com/article,,,\"rust,databases\",\
+            2023-11-14T22:13:20+00:00,2023-11-14T22:13:20+00:00,\n",
+            article.uuid
+        );
+        assert_eq!(expected, csv);
+    }
+
+    #[test]
+    fn to_csv_without_articles_is_empty() {
+        assert_eq!("", to_csv(&[]).unwrap());
+    }
+
+    #[test]
+    fn export_format_from_extension() {
+        assert_eq!(Some(ExportFormat::Csv), ExportFormat::from_extension("CSV"));
+        assert_eq!(
+            Some(ExportFormat::Html),
+            ExportFormat::from_extension("htm")
+        );
+        assert_eq!(None, ExportFormat::from_extension("xml"));
+    }
+}
@@ -0,0 +1,376 @@
+//! RSS 2.0, Atom and JSON feeds of the collection, every article is an
+//! entry linking to the collected url.
+
+use std::io;
+
+use chrono::{DateTime, Utc};
+use domain::articles::Article;
+use quick_xml::{
+    Writer,
+    events::{BytesDecl, BytesText, Event},
+};
+use serde::Serialize;
+
+/// Describes the feed itself, the urls have to be absolute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeedInfo {
+    pub title: String,
+    pub description: String,
+    /// the page of the collection the feed belongs to
+    pub site_url: String,
+    /// the url the feed itself is served from
+    pub feed_url: String,
+}
+
+pub fn rss(info: &FeedInfo, articles: &[Article]) -> String {
+    write_xml(|writer| write_rss(writer, info, articles))
+}
+
+pub fn atom(info: &FeedInfo, articles: &[Article]) -> String {
+    write_xml(|writer| write_atom(writer, info, articles))
+}
+
+/// JSON Feed 1.1, `next_url` points to the following page if there is one.
+pub fn json_feed(
+    info: &FeedInfo,
+    articles: &[Article],
+    next_url: Option<String>,
+) -> Result<String, serde_json::Error> {
+    let feed = JsonFeed {
+        version: JSON_FEED_VERSION,
+        title: &info.title,
+        home_page_url: &info.site_url,
+        feed_url: &info.feed_url,
+        description: &info.description,
+        next_url,
+        items: articles.iter().map(JsonFeedItem::from).collect(),
+    };
+    serde_json::to_string_pretty(&feed)
+}
+
+const JSON_FEED_VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: &'a str,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_url: Option<String>,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: String,
+    url: &'a str,
+    title: &'a str,
+    /// an item needs either text or html content
+    content_text: &'a str,
+    date_published: String,
+    date_modified: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<&'a str>,
+    /// the whole article for clients that know about this collection,
+    /// extensions have to start with an underscore
+    #[serde(rename = "_article")]
+    article: &'a Article,
+}
+
+impl<'a> From<&'a Article> for JsonFeedItem<'a> {
+    fn from(article: &'a Article) -> Self {
+        Self {
+            id: entry_id(article),
+            url: &article.url,
+            title: &article.title,
+            content_text: article.description.as_deref().unwrap_or(&article.title),
+            date_published: article.created_at.to_rfc3339(),
+            date_modified: article.updated_at.to_rfc3339(),
+            tags: article.tags.iter().map(|t| t.name.as_str()).collect(),
+            article,
+        }
+    }
+}
+
+fn write_xml<F>(write: F) -> String
+where
+    F: FnOnce(&mut Writer<Vec<u8>>) -> io::Result<()>,
+{
+    let mut writer = Writer::new_with_indent(vec![], b' ', 2);
+    writer
+        .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+        .and_then(|_| write(&mut writer))
+        .expect("writing into a Vec can't fail");
+    String::from_utf8(writer.into_inner()).expect("only utf-8 strings are written")
+}
+
+fn write_rss(
+    writer: &mut Writer<Vec<u8>>,
+    info: &FeedInfo,
+    articles: &[Article],
+) -> io::Result<()> {
+    writer
+        .create_element("rss")
+        .with_attributes([
+            ("version", "2.0"),
+            ("xmlns:atom", "http://www.w3.org/2005/Atom"),
+        ])
+        .write_inner_content(|writer| {
+            writer
+                .create_element("channel")
+                .write_inner_content(|writer| {
+                    text_element(writer, "title", &info.title)?;
+                    text_element(writer, "link", &info.site_url)?;
+                    text_element(writer, "description", &info.description)?;
+                    writer
+                        .create_element("atom:link")
+                        .with_attributes([
+                            ("href", info.feed_url.as_str()),
+                            ("rel", "self"),
+                            ("type", "application/rss+xml"),
+                        ])
+                        .write_empty()?;
+                    if let Some(updated) = last_updated(articles) {
+                        text_element(writer, "lastBuildDate", &updated.to_rfc2822())?;
+                    }
+                    for article in articles {
+                        write_rss_item(writer, article)?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_rss_item(writer: &mut Writer<Vec<u8>>, article: &Article) -> io::Result<()> {
+    writer
+        .create_element("item")
+        .write_inner_content(|writer| {
+            text_element(writer, "title", &article.title)?;
+            text_element(writer, "link", &article.url)?;
+            writer
+                .create_element("guid")
+                .with_attribute(("isPermaLink", "false"))
+                .write_text_content(BytesText::new(&entry_id(article)))?;
+            text_element(writer, "pubDate", &article.created_at.to_rfc2822())?;
+            if let Some(description) = &article.description {
+                text_element(writer, "description", description)?;
+            }
+            for tag in &article.tags {
+                text_element(writer, "category", &tag.name)?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_atom(
+    writer: &mut Writer<Vec<u8>>,
+    info: &FeedInfo,
+    articles: &[Article],
+) -> io::Result<()> {
+    writer
+        .create_element("feed")
+        .with_attribute(("xmlns", "http://www.w3.org/2005/Atom"))
+        .write_inner_content(|writer| {
+            text_element(writer, "title", &info.title)?;
+            text_element(writer, "subtitle", &info.description)?;
+            text_element(writer, "id", &info.feed_url)?;
+            writer
+                .create_element("link")
+                .with_attributes([("href", info.site_url.as_str()), ("rel", "alternate")])
+                .write_empty()?;
+            writer
+                .create_element("link")
+                .with_attributes([("href", info.feed_url.as_str()), ("rel", "self")])
+                .write_empty()?;
+            // atom requires an updated date even for an empty feed
+            let updated = last_updated(articles).unwrap_or(DateTime::UNIX_EPOCH);
+            text_element(writer, "updated", &updated.to_rfc3339())?;
+            for article in articles {
+                write_atom_entry(writer, article)?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_atom_entry(writer: &mut Writer<Vec<u8>>, article: &Article) -> io::Result<()> {
+    writer
+        .create_element("entry")
+        .write_inner_content(|writer| {
+            text_element(writer, "title", &article.title)?;
+            writer
+                .create_element("link")
+                .with_attributes([("href", article.url.as_str()), ("rel", "alternate")])
+                .write_empty()?;
+            text_element(writer, "id", &entry_id(article))?;
+            text_element(writer, "published", &article.created_at.to_rfc3339())?;
+            text_element(writer, "updated", &article.updated_at.to_rfc3339())?;
+            if let Some(description) = &article.description {
+                text_element(writer, "summary", description)?;
+            }
+            for tag in &article.tags {
+                writer
+                    .create_element("category")
+                    .with_attribute(("term", tag.name.as_str()))
+                    .write_empty()?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn text_element(writer: &mut Writer<Vec<u8>>, name: &str, text: &str) -> io::Result<()> {
+    writer
+        .create_element(name)
+        .write_text_content(BytesText::new(text))?;
+    Ok(())
+}
+
+/// Entries are identified by the uuid of the article, so they stay the same
+/// when the url or title is edited.
+fn entry_id(article: &Article) -> String {
+    format!("urn:uuid:{}", article.uuid)
+}
+
+fn last_updated(articles: &[Article]) -> Option<DateTime<Utc>> {
+    articles.iter().map(|a| a.updated_at).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use domain::{articles::Article, tags::parse_tag_list};
+    use uuid::Uuid;
+
+    use serde_json::{Value, json};
+
+    use crate::feeds::{FeedInfo, atom, json_feed, rss};
+
+    fn info() -> FeedInfo {
+        FeedInfo {
+            title: String::from("Articles"),
+            description: String::from("Collected articles"),
+            site_url: String::from("https://articles.example.com/articles"),
+            feed_url: String::from("https://articles.example.com/feed.rss"),
+        }
+    }
+
+    fn article() -> Article {
+        let mut article = Article::from_parts(
+            String::from("Tom & Jerry <3"),
+            String::from("https://example.com/?a=1&b=2"),
+        )
+        .with_tags(parse_tag_list("cartoons"));
+        article.uuid = Uuid::nil();
+        article.created_at = DateTime::from_timestamp(1700000000, 0).unwrap();
+        article.updated_at = DateTime::from_timestamp(1700000100, 0).unwrap();
+        article.description = Some(String::from("A \"classic\""));
+        article
+    }
+
+    #[test]
+    fn rss_feed() {
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+  <channel>
+    <title>Articles</title>
+    <link>https://articles.example.com/articles</link>
+    <description>Collected articles</description>
+    <atom:link href="https://articles.example.com/feed.rss" rel="self" type="application/rss+xml"/>
+    <lastBuildDate>Tue, 14 Nov 2023 22:15:00 +0000</lastBuildDate>
+    <item>
+      <title>Tom &amp; Jerry &lt;3</title>
+      <link>https://example.com/?a=1&amp;b=2</link>
+      <guid isPermaLink="false">urn:uuid:00000000-0000-0000-0000-000000000000</guid>
+      <pubDate>Tue, 14 Nov 2023 22:13:20 +0000</pubDate>
+      <description>A &quot;classic&quot;</description>
+      <category>cartoons</category>
+    </item>
+  </channel>
+</rss>"#;
+        assert_eq!(expected, rss(&info(), &[article()]));
+    }
+
+    #[test]
+    fn atom_feed() {
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Articles</title>
+  <subtitle>Collected articles</subtitle>
+  <id>https://articles.example.com/feed.rss</id>
+  <link href="https://articles.example.com/articles" rel="alternate"/>
+  <link href="https://articles.example.com/feed.rss" rel="self"/>
+  <updated>2023-11-14T22:15:00+00:00</updated>
+  <entry>
+    <title>Tom &amp; Jerry &lt;3</title>
+    <link href="https://example.com/?a=1&amp;b=2" rel="alternate"/>
+    <id>urn:uuid:00000000-0000-0000-0000-000000000000</id>
+    <published>2023-11-14T22:13:20+00:00</published>
+    <updated>2023-11-14T22:15:00+00:00</updated>
+    <summary>A &quot;classic&quot;</summary>
+    <category term="cartoons"/>
+  </entry>
+</feed>"#;
+        assert_eq!(expected, atom(&info(), &[article()]));
+    }
+
+    #[test]
+    fn json_feed_with_next_page() {
+        let article = article();
+        let feed = json_feed(
+            &info(),
+            std::slice::from_ref(&article),
+            Some(String::from(
+                "https://articles.example.com/feed.json?after=1",
+            )),
+        )
+        .unwrap();
+        let feed = serde_json::from_str::<Value>(&feed).unwrap();
+
+        assert_eq!(
+            json!({
+                "version": "https://jsonfeed.org/version/1.1",
+                "title": "Articles",
+                "home_page_url": "https://articles.example.com/articles",
+                "feed_url": "https://articles.example.com/feed.rss",
+                "description": "Collected articles",
+                "next_url": "https://articles.example.com/feed.json?after=1",
+                "items": [{
+                    "id": "urn:uuid:00000000-0000-0000-0000-000000000000",
+                    "url": "https://example.com/?a=1&b=2",
+                    "title": "Tom & Jerry <3",
+                    "content_text": "A \"classic\"",
+                    "date_published": "2023-11-14T22:13:20+00:00",
+                    "date_modified": "2023-11-14T22:15:00+00:00",
+                    "tags": ["cartoons"],
+                    "_article": serde_json::to_value(&article).unwrap(),
+                }],
+            }),
+            feed
+        );
+    }
+
+    #[test]
+    fn json_feed_last_page_has_no_next_url() {
+        let mut article = article();
+        article.description = None;
+        article.tags = vec![];
+        let feed = json_feed(&info(), &[article], None).unwrap();
+        let feed = serde_json::from_str::<Value>(&feed).unwrap();
+
+        assert_eq!(None, feed.get("next_url"));
+        assert_eq!(json!("Tom & Jerry <3"), feed["items"][0]["content_text"]);
+        assert_eq!(None, feed["items"][0].get("tags"));
+    }
+
+    #[test]
+    fn empty_feeds_are_valid() {
+        assert!(rss(&info(), &[]).ends_with("</channel>\n</rss>"));
+        assert!(atom(&info(), &[]).contains("<updated>1970-01-01T00:00:00+00:00</updated>"));
+    }
+}
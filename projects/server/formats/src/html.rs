@@ -0,0 +1,74 @@
+//! Helpers shared by the formats that read or write html. Html is always
+//! parsed with `scraper`, which copes with broken html like browsers do.
+
+use scraper::{ElementRef, Html};
+
+/// The text of an html fragment without any tags, with whitespace collapsed
+/// and entities decoded.
+pub(crate) fn text_content(html: &str) -> String {
+    element_text(Html::parse_fragment(html).root_element())
+}
+
+/// The text of the element and all its descendants, with whitespace
+/// collapsed.
+pub(crate) fn element_text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escapes text so it can be used as text or quoted attribute value.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::html::{escape, text_content};
+
+    #[test]
+    fn text_content_decodes_entities() {
+        assert_eq!(
+            "Tom & Jerry's <\"show\">",
+            text_content("Tom &amp; Jerry&#39;s &lt;&quot;show&quot;&#x3E;")
+        );
+    }
+
+    #[test]
+    fn text_content_keeps_unknown_entities() {
+        assert_eq!("a &unknown; b & c", text_content("a &unknown; b & c"));
+    }
+
+    #[test]
+    fn text_content_strips_tags_and_whitespace() {
+        assert_eq!(
+            "Rust is great",
+            text_content("<p>Rust <b>is</b>\n  great</p>")
+        );
+        assert_eq!("foobar", text_content("foo<b>bar</b>"));
+    }
+
+    #[test]
+    fn escape_round_trips() {
+        let text = "<a href=\"x\">Tom & Jerry's</a>";
+        assert_eq!(
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;",
+            escape(text)
+        );
+        assert_eq!(text, text_content(&escape(text)));
+    }
+}
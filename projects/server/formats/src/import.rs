@@ -0,0 +1,186 @@
+//! Imports of the export files of other services. Every service gets its
+//! own [`Importer`] that reads the file into [`Record`]s, which are then
+//! turned into articles and inserted like any other submission.
+
+use std::{error::Error, fmt::Display};
+
+use chrono::{DateTime, Utc};
+use domain::{
+    articles::Article,
+    paste::{InvalidLink, get_title_from_url},
+    tags::tags_from_names,
+    urls::normalize_url,
+};
+
+pub trait Importer {
+    /// Name of the service the files are exported from
+    fn name(&self) -> &'static str;
+
+    /// Reads all records of an export file, in the order of the file.
+    fn parse(&self, contents: &str) -> Result<Vec<Record>, ImportError>;
+}
+
+/// A single saved link of an export file, mapped onto what the articles
+/// store.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Record {
+    pub title: String,
+    pub url: String,
+    pub added_at: Option<DateTime<Utc>>,
+    pub read_at: Option<DateTime<Utc>>,
+    /// tag names as the service had them, they get normalized on import
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+}
+
+impl Record {
+    pub fn into_article(self) -> Result<Article, InvalidLink> {
+        if let Err(error) = normalize_url(&self.url) {
+            return Err(InvalidLink {
+                text: self.url,
+                error,
+            });
+        }
+
+        let title = match self.title.trim().is_empty() {
+            true => get_title_from_url(self.url.clone()),
+            false => self.title.trim().to_string(),
+        };
+        let tags = tags_from_names(self.tags.iter().map(String::as_str));
+
+        let mut article = Article::from_parts(title, self.url).with_tags(tags);
+        article.description = self.description.filter(|d| !d.trim().is_empty());
+        if let Some(added_at) = self.added_at {
+            article.created_at = added_at;
+            article.updated_at = added_at;
+        }
+        article.read_at = self.read_at;
+        Ok(article)
+    }
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Csv(csv::Error),
+    /// The file isn't in the format the importer expects
+    UnknownFormat(&'static str),
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Csv(err) => write!(f, "invalid csv: {err}"),
+            ImportError::UnknownFormat(expected) => {
+                write!(f, "not a {expected} export file")
+            }
+        }
+    }
+}
+
+impl Error for ImportError {}
+
+impl From<csv::Error> for ImportError {
+    fn from(value: csv::Error) -> Self {
+        ImportError::Csv(value)
+    }
+}
+
+/// Html exports start with a tag, csv exports with their header.
+pub(crate) fn is_html(contents: &str) -> bool {
+    contents.trim_start().starts_with('<')
+}
+
+/// Deserializes every row of a csv file with a header into `T`.
+pub(crate) fn csv_rows<T>(contents: &str) -> Result<Vec<T>, ImportError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes());
+    Ok(reader.deserialize().collect::<Result<Vec<T>, _>>()?)
+}
+
+/// Unix timestamps in seconds, empty and invalid values are `None`.
+pub(crate) fn parse_unix_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|t| *t > 0)
+        .and_then(|t| DateTime::from_timestamp(t, 0))
+}
+
+pub(crate) fn split_tags(tags: &str, separator: char) -> Vec<String> {
+    tags.split(separator)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::import::{Record, parse_unix_timestamp, split_tags};
+
+    #[test]
+    fn into_article_maps_all_fields() {
+        let added_at = DateTime::from_timestamp(1700000000, 0);
+        let read_at = DateTime::from_timestamp(1700000100, 0);
+        let article = Record {
+            title: String::from(" Title "),
+            url: String::from("https://example.com/article"),
+            added_at,
+            read_at,
+            tags: vec![
+                String::from("Rust"),
+                String::from("rust"),
+                String::from("Read Later"),
+            ],
+            description: Some(String::from("  ")),
+        }
+        .into_article()
+        .unwrap();
+
+        assert_eq!("Title", article.title);
+        assert_eq!(added_at.unwrap(), article.created_at);
+        assert_eq!(added_at.unwrap(), article.updated_at);
+        assert_eq!(read_at, article.read_at);
+        assert_eq!(None, article.description);
+        let tags = article
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["rust", "read-later"], tags);
+    }
+
+    #[test]
+    fn into_article_rejects_invalid_urls() {
+        let invalid = Record {
+            url: String::from("javascript:alert(1)"),
+            ..Default::default()
+        }
+        .into_article()
+        .unwrap_err();
+        assert_eq!("javascript:alert(1)", invalid.text);
+    }
+
+    #[test]
+    fn parse_unix_timestamp_ignores_invalid() {
+        assert_eq!(
+            DateTime::from_timestamp(1700000000, 0),
+            parse_unix_timestamp(" 1700000000 ")
+        );
+        assert_eq!(None, parse_unix_timestamp(""));
+        assert_eq!(None, parse_unix_timestamp("0"));
+    }
+
+    #[test]
+    fn split_tags_skips_empty() {
+        assert_eq!(vec!["a", "b c"], split_tags("a| |b c|", '|'));
+    }
+}
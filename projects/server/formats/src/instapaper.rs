@@ -0,0 +1,133 @@
+//! Instapaper exports a csv file with the columns `URL, Title, Selection,
+//! Folder, Timestamp` and in newer exports `Tags`. Read articles are in the
+//! "Archive" folder, every other folder besides "Unread" becomes a tag.
+
+use serde::Deserialize;
+
+use crate::import::{ImportError, Importer, Record, csv_rows, is_html, parse_unix_timestamp};
+
+pub struct InstapaperImporter;
+
+impl Importer for InstapaperImporter {
+    fn name(&self) -> &'static str {
+        "Instapaper"
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<Record>, ImportError> {
+        if is_html(contents) {
+            return Err(ImportError::UnknownFormat("Instapaper csv"));
+        }
+
+        let rows = csv_rows::<InstapaperRow>(contents)?;
+        Ok(rows.into_iter().map(Record::from).collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct InstapaperRow {
+    #[serde(rename = "URL")]
+    url: String,
+    #[serde(rename = "Title", default)]
+    title: String,
+    #[serde(rename = "Selection", default)]
+    selection: String,
+    #[serde(rename = "Folder", default)]
+    folder: String,
+    #[serde(rename = "Timestamp", default)]
+    timestamp: String,
+    /// a json list like `["rust","databases"]`
+    #[serde(rename = "Tags", default)]
+    tags: String,
+}
+
+impl From<InstapaperRow> for Record {
+    fn from(row: InstapaperRow) -> Self {
+        let added_at = parse_unix_timestamp(&row.timestamp);
+        let folder = row.folder.trim();
+        let archived = folder.eq_ignore_ascii_case("archive");
+
+        let mut tags = parse_tags(&row.tags);
+        if !archived && !folder.eq_ignore_ascii_case("unread") && !folder.is_empty() {
+            tags.insert(0, folder.to_string());
+        }
+
+        Record {
+            title: row.title,
+            url: row.url,
+            added_at,
+            // Instapaper doesn't know when something was read
+            read_at: archived.then_some(added_at).flatten(),
+            tags,
+            description: Some(row.selection),
+        }
+    }
+}
+
+fn parse_tags(tags: &str) -> Vec<String> {
+    let tags = tags.trim();
+    match tags.starts_with('[') {
+        true => serde_json::from_str(tags).unwrap_or_default(),
+        false => crate::import::split_tags(tags, ','),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::{
+        import::{Importer, Record},
+        instapaper::InstapaperImporter,
+    };
+
+    #[test]
+    fn parse_export() {
+        let csv = r#"URL,Title,Selection,Folder,Timestamp,Tags
+https://example.com/unread,Unread,,Unread,1700000000,"[""rust"",""databases""]"
+https://example.com/read,Read,Highlighted text,Archive,1600000000,[]
+https://example.com/starred,Starred,,Starred,1500000000,
+"#;
+        assert_eq!(
+            vec![
+                Record {
+                    title: String::from("Unread"),
+                    url: String::from("https://example.com/unread"),
+                    added_at: DateTime::from_timestamp(1700000000, 0),
+                    tags: vec![String::from("rust"), String::from("databases")],
+                    description: Some(String::new()),
+                    ..Default::default()
+                },
+                Record {
+                    title: String::from("Read"),
+                    url: String::from("https://example.com/read"),
+                    added_at: DateTime::from_timestamp(1600000000, 0),
+                    read_at: DateTime::from_timestamp(1600000000, 0),
+                    description: Some(String::from("Highlighted text")),
+                    ..Default::default()
+                },
+                Record {
+                    title: String::from("Starred"),
+                    url: String::from("https://example.com/starred"),
+                    added_at: DateTime::from_timestamp(1500000000, 0),
+                    tags: vec![String::from("Starred")],
+                    description: Some(String::new()),
+                    ..Default::default()
+                },
+            ],
+            InstapaperImporter.parse(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_old_export_without_tags() {
+        let csv = "URL,Title,Selection,Folder,Timestamp\nhttps://example.com,Example,,Unread,1700000000\n";
+        let records = InstapaperImporter.parse(csv).unwrap();
+        assert_eq!(1, records.len());
+        assert!(records[0].tags.is_empty());
+    }
+
+    #[test]
+    fn parse_html_fails() {
+        assert!(InstapaperImporter.parse("<html></html>").is_err());
+    }
+}
@@ -0,0 +1,13 @@
+pub mod export;
+pub mod feeds;
+pub mod import;
+pub mod instapaper;
+pub mod metadata;
+pub mod netscape;
+pub mod pocket;
+pub mod raindrop;
+pub mod reader;
+pub mod snapshot;
+pub mod subscriptions;
+
+mod html;
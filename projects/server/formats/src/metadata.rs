@@ -0,0 +1,202 @@
+//! The metadata in the `<head>` of a web page: its `<title>`, the OpenGraph
+//! and Twitter card tags and the canonical and icon links.
+
+use scraper::{ElementRef, Html};
+
+use crate::html::element_text;
+
+/// What a page says about itself, everything the page doesn't set or sets
+/// to an empty value is `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub site_name: Option<String>,
+    /// Can be relative to the url of the page
+    pub canonical_url: Option<String>,
+    /// Preview image of the page, can be relative as well
+    pub image: Option<String>,
+    /// Favicon of the site, can be relative as well
+    pub icon: Option<String>,
+}
+
+/// Reads the metadata of an html page. OpenGraph tags are preferred over
+/// Twitter card tags, which are preferred over the plain html ones.
+///
+/// Tags are read from the whole page, as the html parser moves them into
+/// the body when the head contains something else.
+pub fn parse(html: &str) -> PageMetadata {
+    let document = Html::parse_document(html);
+    let mut found = Found::default();
+
+    for element in document.root_element().descendent_elements() {
+        match element.value().name() {
+            "title" if !is_in_svg(element) => {
+                found.title.get_or_insert(element_text(element));
+            }
+            "meta" => {
+                let Some(content) = element.attr("content") else {
+                    continue;
+                };
+                let key = element
+                    .attr("property")
+                    .or(element.attr("name"))
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                let field = match key.as_str() {
+                    "og:title" => &mut found.og_title,
+                    "twitter:title" => &mut found.twitter_title,
+                    "og:description" => &mut found.og_description,
+                    "twitter:description" => &mut found.twitter_description,
+                    "description" => &mut found.description,
+                    "og:site_name" => &mut found.site_name,
+                    "og:url" => &mut found.og_url,
+                    "og:image" | "og:image:url" => &mut found.og_image,
+                    "twitter:image" | "twitter:image:src" => &mut found.twitter_image,
+                    _ => continue,
+                };
+                field.get_or_insert(content.to_string());
+            }
+            "link" => {
+                let (Some(rel), Some(href)) = (element.attr("rel"), element.attr("href")) else {
+                    continue;
+                };
+                let has_rel =
+                    |name: &str| rel.split_whitespace().any(|r| r.eq_ignore_ascii_case(name));
+                let field = if has_rel("canonical") {
+                    &mut found.canonical
+                } else if has_rel("icon") {
+                    &mut found.icon
+                } else if has_rel("apple-touch-icon") {
+                    &mut found.apple_touch_icon
+                } else {
+                    continue;
+                };
+                field.get_or_insert(href.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    found.into_metadata()
+}
+
+#[derive(Default)]
+struct Found {
+    title: Option<String>,
+    og_title: Option<String>,
+    twitter_title: Option<String>,
+    description: Option<String>,
+    og_description: Option<String>,
+    twitter_description: Option<String>,
+    site_name: Option<String>,
+    canonical: Option<String>,
+    og_url: Option<String>,
+    og_image: Option<String>,
+    twitter_image: Option<String>,
+    icon: Option<String>,
+    apple_touch_icon: Option<String>,
+}
+
+impl Found {
+    fn into_metadata(self) -> PageMetadata {
+        PageMetadata {
+            title: first_set([self.og_title, self.twitter_title, self.title]),
+            description: first_set([
+                self.og_description,
+                self.twitter_description,
+                self.description,
+            ]),
+            site_name: first_set([self.site_name]),
+            canonical_url: first_set([self.canonical, self.og_url]),
+            image: first_set([self.og_image, self.twitter_image]),
+            icon: first_set([self.icon, self.apple_touch_icon]),
+        }
+    }
+}
+
+/// Titles of svgs name an icon, not the page.
+fn is_in_svg(element: ElementRef) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| ancestor.value().name() == "svg")
+}
+
+fn first_set<const N: usize>(values: [Option<String>; N]) -> Option<String> {
+    values
+        .into_iter()
+        .flatten()
+        .map(|value| value.split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metadata::{PageMetadata, parse};
+
+    #[test]
+    fn prefers_open_graph() {
+        let metadata = parse(
+            r#"<!DOCTYPE html>
+            <html>
+              <head>
+                <title>Plain title | Blog</title>
+                <meta name="description" content="plain description">
+                <meta name="twitter:title" content="Twitter title">
+                <META PROPERTY="og:title" CONTENT="Open &amp; Graph">
+                <meta property="og:description" content="  og
+                    description ">
+                <meta property="og:site_name" content="Blog">
+                <meta property="og:url" content="https://blog.example.com/og">
+                <link rel="canonical" href="/post">
+                <link rel="apple-touch-icon" href="/apple-touch-icon.png">
+                <link rel="shortcut icon" href="/favicon.png">
+                <meta name="twitter:image" content="/twitter.png">
+                <meta property="og:image" content="https://cdn.example.com/og.png">
+              </head>
+              <body><svg><title>Icon</title></svg></body>
+            </html>"#,
+        );
+
+        assert_eq!(
+            PageMetadata {
+                title: Some(String::from("Open & Graph")),
+                description: Some(String::from("og description")),
+                site_name: Some(String::from("Blog")),
+                canonical_url: Some(String::from("/post")),
+                image: Some(String::from("https://cdn.example.com/og.png")),
+                icon: Some(String::from("/favicon.png")),
+            },
+            metadata
+        );
+    }
+
+    #[test]
+    fn falls_back_to_plain_html() {
+        let metadata = parse(
+            "<html><head><title>\n  Plain &lt;title&gt;\n</title>\
+            <meta name=description content='Plain description'>\
+            <meta property=og:title content=''>\
+            <link rel=apple-touch-icon href=/touch.png></head></html>",
+        );
+
+        assert_eq!(
+            PageMetadata {
+                title: Some(String::from("Plain <title>")),
+                description: Some(String::from("Plain description")),
+                site_name: None,
+                canonical_url: None,
+                image: None,
+                icon: Some(String::from("/touch.png")),
+            },
+            metadata
+        );
+    }
+
+    #[test]
+    fn ignores_the_body() {
+        let metadata = parse("<html><body><svg><title>Icon</title></svg></body></html>");
+        assert_eq!(PageMetadata::default(), metadata);
+    }
+}
@@ -0,0 +1,432 @@
+//! The Netscape bookmark file format, `bookmarks.html`, which every browser
+//! and most bookmark services can export.
+//!
+//! ```html
+//! <DL><p>
+//!     <DT><H3 ADD_DATE="1700000000">Rust</H3>
+//!     <DL><p>
+//!         <DT><A HREF="https://blog.rust-lang.org" ADD_DATE="1700000000" TAGS="news">Rust Blog</A>
+//!         <DD>Official blog
+//!     </DL><p>
+//! </DL><p>
+//! ```
+
+use chrono::{DateTime, Utc};
+use domain::articles::Article;
+use scraper::{ElementRef, Html};
+
+use crate::{
+    html::{element_text, escape},
+    import::{ImportError, Importer, Record},
+};
+
+/// A single bookmark of a bookmark file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+    pub added_at: Option<DateTime<Utc>>,
+    /// names of the folders the bookmark is in, outermost first
+    pub folders: Vec<String>,
+    /// tags of services that support them, like Firefox or Pinboard
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// Folders and tags of the bookmark both become tags.
+impl From<Bookmark> for Record {
+    fn from(bookmark: Bookmark) -> Self {
+        Record {
+            title: bookmark.title,
+            url: bookmark.url,
+            added_at: bookmark.added_at,
+            read_at: None,
+            tags: bookmark.folders.into_iter().chain(bookmark.tags).collect(),
+            description: bookmark.description,
+        }
+    }
+}
+
+pub struct NetscapeImporter;
+
+impl Importer for NetscapeImporter {
+    fn name(&self) -> &'static str {
+        "Browser bookmarks"
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<Record>, ImportError> {
+        Ok(parse(contents).into_iter().map(Record::from).collect())
+    }
+}
+
+/// Folders browsers create on their own aren't worth a tag.
+const SPECIAL_FOLDER_ATTRIBUTES: [&str; 2] =
+    ["personal_toolbar_folder", "unfiled_bookmarks_folder"];
+
+/// Reads all bookmarks of a bookmark file. The format is rarely valid html,
+/// the html parser closes the `<DT>`, `<DD>` and `<p>` tags like browsers do
+/// and everything but folders, bookmarks and their descriptions is skipped.
+pub fn parse(html: &str) -> Vec<Bookmark> {
+    let document = Html::parse_document(html);
+    let mut parser = Parser::default();
+    parser.visit(document.root_element());
+    parser.bookmarks
+}
+
+#[derive(Default)]
+struct Parser {
+    bookmarks: Vec<Bookmark>,
+    folders: Vec<Option<String>>,
+    /// name of the last `<H3>`, the folder of the following `<DL>`
+    next_folder: Option<String>,
+    /// a `<DD>` only describes the bookmark directly in front of it
+    after_bookmark: bool,
+}
+
+impl Parser {
+    fn visit(&mut self, element: ElementRef) {
+        match element.value().name() {
+            "h3" => {
+                let name = element_text(element);
+                let special = SPECIAL_FOLDER_ATTRIBUTES
+                    .iter()
+                    .any(|a| element.attr(a).is_some());
+                self.next_folder = (!special && !name.is_empty()).then_some(name);
+                self.after_bookmark = false;
+            }
+            "dl" => {
+                self.folders.push(self.next_folder.take());
+                self.after_bookmark = false;
+                self.visit_children(element);
+                self.folders.pop();
+                self.after_bookmark = false;
+            }
+            "a" => {
+                self.after_bookmark = false;
+                let Some(url) = element.attr("href").map(str::trim) else {
+                    return;
+                };
+                self.bookmarks.push(Bookmark {
+                    title: element_text(element),
+                    url: url.into(),
+                    added_at: element.attr("add_date").and_then(parse_timestamp),
+                    folders: self.folders.iter().flatten().cloned().collect(),
+                    tags: element
+                        .attr("tags")
+                        .map(|tags| tags.split(',').map(|t| t.trim().into()).collect())
+                        .unwrap_or_default(),
+                    description: None,
+                });
+                self.after_bookmark = true;
+            }
+            "dd" => {
+                let bookmark = self.bookmarks.last_mut().filter(|_| self.after_bookmark);
+                if let Some(bookmark) = bookmark {
+                    // the description is the text in front of a nested list
+                    let description = element
+                        .children()
+                        .map_while(|child| child.value().as_text().map(|text| &**text))
+                        .collect::<String>()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    bookmark.description = (!description.is_empty()).then_some(description);
+                }
+                self.after_bookmark = false;
+                self.visit_children(element);
+            }
+            "dt" => {
+                self.after_bookmark = false;
+                self.visit_children(element);
+            }
+            _ => self.visit_children(element),
+        }
+    }
+
+    fn visit_children(&mut self, element: ElementRef) {
+        for child in element.child_elements() {
+            self.visit(child);
+        }
+    }
+}
+
+/// Writes the articles as a bookmark file that browsers can import. The
+/// tags end up in the `TAGS` attribute, the description in a `<DD>`.
+pub fn to_html(articles: &[Article]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+",
+    );
+
+    for article in articles {
+        let tags = article
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        html.push_str(&format!(
+            "    <DT><A HREF=\"{}\" ADD_DATE=\"{}\" LAST_MODIFIED=\"{}\"",
+            escape(&article.url),
+            article.created_at.timestamp(),
+            article.updated_at.timestamp(),
+        ));
+        if !tags.is_empty() {
+            html.push_str(&format!(" TAGS=\"{}\"", escape(&tags)));
+        }
+        html.push_str(&format!(">{}</A>\n", escape(&article.title)));
+        if let Some(description) = &article.description {
+            html.push_str(&format!("    <DD>{}\n", escape(description)));
+        }
+    }
+
+    html.push_str("</DL><p>\n");
+    html
+}
+
+/// `ADD_DATE` is supposed to be in seconds, but some exports use milli- or
+/// microseconds.
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let mut timestamp = value.trim().parse::<i64>().ok()?;
+    while timestamp > 100_000_000_000 {
+        timestamp /= 1000;
+    }
+    (timestamp > 0)
+        .then(|| DateTime::from_timestamp(timestamp, 0))
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use domain::{articles::Article, tags::parse_tag_list};
+
+    use crate::{
+        import::Record,
+        netscape::{Bookmark, parse, parse_timestamp, to_html},
+    };
+
+    const FIREFOX: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks Menu</H1>
+
+<DL><p>
+    <DT><A HREF="https://www.rust-lang.org/" ADD_DATE="1600000000" LAST_MODIFIED="1600000001">Rust Programming Language</A>
+    <DT><H3 ADD_DATE="1600000000" LAST_MODIFIED="1600000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks Toolbar</H3>
+    <DL><p>
+        <DT><A HREF="https://news.ycombinator.com/" ADD_DATE="1610000000" TAGS="news,tech">Hacker News</A>
+        <DT><H3 ADD_DATE="1600000000">Databases</H3>
+        <DL><p>
+            <DT><A HREF="https://www.postgresql.org/docs/" ADD_DATE="1620000000">PostgreSQL &amp; friends</A>
+            <DD>The docs
+            <DT><H3>Papers</H3>
+            <DL><p>
+                <DT><A HREF="https://db.cs.cmu.edu/papers/" ADD_DATE="1630000000000000">CMU Papers</A>
+            </DL><p>
+        </DL><p>
+        <DT><A HREF="place:sort=8&maxResults=10" ADD_DATE="1600000000">Recent Tags</A>
+    </DL><p>
+</DL>
+"#;
+
+    fn bookmark(title: &str, url: &str, added_at: i64, folders: &[&str]) -> Bookmark {
+        Bookmark {
+            title: title.into(),
+            url: url.into(),
+            added_at: DateTime::from_timestamp(added_at, 0),
+            folders: folders.iter().map(|f| f.to_string()).collect(),
+            tags: vec![],
+            description: None,
+        }
+    }
+
+    #[test]
+    fn parse_firefox_export() {
+        let bookmarks = parse(FIREFOX);
+        assert_eq!(
+            vec![
+                bookmark(
+                    "Rust Programming Language",
+                    "https://www.rust-lang.org/",
+                    1600000000,
+                    &[]
+                ),
+                Bookmark {
+                    tags: vec![String::from("news"), String::from("tech")],
+                    ..bookmark(
+                        "Hacker News",
+                        "https://news.ycombinator.com/",
+                        1610000000,
+                        &[]
+                    )
+                },
+                Bookmark {
+                    description: Some(String::from("The docs")),
+                    ..bookmark(
+                        "PostgreSQL & friends",
+                        "https://www.postgresql.org/docs/",
+                        1620000000,
+                        &["Databases"]
+                    )
+                },
+                bookmark(
+                    "CMU Papers",
+                    "https://db.cs.cmu.edu/papers/",
+                    1630000000,
+                    &["Databases", "Papers"]
+                ),
+                bookmark("Recent Tags", "place:sort=8&maxResults=10", 1600000000, &[]),
+            ],
+            bookmarks
+        );
+    }
+
+    #[test]
+    fn parse_chrome_export_without_paragraphs() {
+        let html = r#"<DL>
+<DT><H3 ADD_DATE="1700000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+<DL>
+<DT><H3>Reading List</H3>
+<DL>
+<DT><A HREF='https://example.com/a' ADD_DATE=1700000000 ICON="data:image/png;base64,AAA=">A</A>
+</DL>
+<DT><A HREF="https://example.com/b">B</A>
+</DL>
+</DL>"#;
+        assert_eq!(
+            vec![
+                bookmark("A", "https://example.com/a", 1700000000, &["Reading List"]),
+                Bookmark {
+                    added_at: None,
+                    ..bookmark("B", "https://example.com/b", 0, &[])
+                },
+            ],
+            parse(html)
+        );
+    }
+
+    #[test]
+    fn parse_folder_descriptions() {
+        let html = r#"<DL><p>
+    <DT><H3>Rust</H3>
+    <DD>Everything &amp; more
+    <DL><p>
+        <DT><A HREF="https://blog.rust-lang.org">Rust Blog</A>
+        <DD>Official blog
+    </DL><p>
+</DL><p>"#;
+        assert_eq!(
+            vec![Bookmark {
+                description: Some(String::from("Official blog")),
+                added_at: None,
+                ..bookmark("Rust Blog", "https://blog.rust-lang.org", 0, &["Rust"])
+            }],
+            parse(html)
+        );
+    }
+
+    #[test]
+    fn parse_empty_file() {
+        assert!(parse("").is_empty());
+        assert!(parse("<DL><p></DL><p>").is_empty());
+    }
+
+    #[test]
+    fn parse_timestamp_units() {
+        let expected = DateTime::from_timestamp(1700000000, 0);
+        assert_eq!(expected, parse_timestamp("1700000000"));
+        assert_eq!(expected, parse_timestamp("1700000000000"));
+        assert_eq!(expected, parse_timestamp("1700000000000000"));
+        assert_eq!(None, parse_timestamp("0"));
+        assert_eq!(None, parse_timestamp("yesterday"));
+    }
+
+    #[test]
+    fn into_article_maps_folders_and_tags() {
+        let article = Record::from(Bookmark {
+            tags: vec![String::from("Postgres"), String::from("databases")],
+            description: Some(String::from("The docs")),
+            ..bookmark(
+                "PostgreSQL",
+                "https://www.postgresql.org/docs/",
+                1620000000,
+                &["Databases", "Read Later"],
+            )
+        })
+        .into_article()
+        .unwrap();
+
+        let tags = article
+            .tags
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["databases", "read-later", "postgres"], tags);
+        assert_eq!(
+            DateTime::from_timestamp(1620000000, 0).unwrap(),
+            article.created_at
+        );
+        assert_eq!(Some(String::from("The docs")), article.description);
+    }
+
+    #[test]
+    fn into_article_without_title() {
+        let article = Record::from(bookmark(
+            "",
+            "https://github.com/mrkline/modern-latex",
+            0,
+            &[],
+        ))
+        .into_article()
+        .unwrap();
+        assert_eq!("github.com - modern-latex", article.title);
+    }
+
+    #[test]
+    fn into_article_rejects_browser_urls() {
+        let invalid = Record::from(bookmark("Recent Tags", "place:sort=8", 0, &[]))
+            .into_article()
+            .unwrap_err();
+        assert_eq!("place:sort=8", invalid.text);
+    }
+
+    #[test]
+    fn to_html_round_trips() {
+        let mut article = Article::from_parts(
+            String::from("Tom & Jerry <3"),
+            String::from("https://example.com/?a=1&b=\"2\""),
+        )
+        .with_tags(parse_tag_list("cartoons, to share"));
+        article.created_at = DateTime::from_timestamp(1700000000, 0).unwrap();
+        article.description = Some(String::from("A \"classic\""));
+        let plain = Article::from_parts(
+            String::from("Plain"),
+            String::from("https://example.com/plain"),
+        );
+
+        let bookmarks = parse(&to_html(&[article.clone(), plain.clone()]));
+        assert_eq!(
+            vec![
+                Bookmark {
+                    tags: vec![String::from("cartoons"), String::from("to-share")],
+                    description: article.description.clone(),
+                    ..bookmark(&article.title, &article.url, 1700000000, &[])
+                },
+                bookmark(&plain.title, &plain.url, plain.created_at.timestamp(), &[]),
+            ],
+            bookmarks
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Pocket exports either an html file with an "Unread" and a "Read Archive"
+//! list or, since 2024, a csv file with a `status` column.
+
+use scraper::Html;
+use serde::Deserialize;
+
+use crate::{
+    html::element_text,
+    import::{ImportError, Importer, Record, csv_rows, is_html, parse_unix_timestamp, split_tags},
+};
+
+pub struct PocketImporter;
+
+impl Importer for PocketImporter {
+    fn name(&self) -> &'static str {
+        "Pocket"
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<Record>, ImportError> {
+        match is_html(contents) {
+            true => Ok(parse_html(contents)),
+            false => parse_csv(contents),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PocketRow {
+    #[serde(default)]
+    title: String,
+    url: String,
+    #[serde(default)]
+    time_added: String,
+    #[serde(default)]
+    tags: String,
+    #[serde(default)]
+    status: String,
+}
+
+fn parse_csv(contents: &str) -> Result<Vec<Record>, ImportError> {
+    let rows = csv_rows::<PocketRow>(contents)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let added_at = parse_unix_timestamp(&row.time_added);
+            Record {
+                title: row.title,
+                url: row.url,
+                added_at,
+                // Pocket doesn't know when something was read
+                read_at: row
+                    .status
+                    .eq_ignore_ascii_case("archive")
+                    .then_some(added_at)
+                    .flatten(),
+                tags: split_tags(&row.tags, '|'),
+                description: None,
+            }
+        })
+        .collect())
+}
+
+fn parse_html(contents: &str) -> Vec<Record> {
+    let document = Html::parse_document(contents);
+    let mut records = vec![];
+    let mut archived = false;
+
+    for element in document.root_element().descendent_elements() {
+        match element.value().name() {
+            "h1" => archived = element_text(element).to_lowercase().contains("archive"),
+            "a" => {
+                let Some(url) = element.attr("href") else {
+                    continue;
+                };
+                let added_at = element.attr("time_added").and_then(parse_unix_timestamp);
+                records.push(Record {
+                    title: element_text(element),
+                    url: url.trim().into(),
+                    added_at,
+                    read_at: archived.then_some(added_at).flatten(),
+                    tags: split_tags(element.attr("tags").unwrap_or_default(), ','),
+                    description: None,
+                });
+            }
+            _ => {}
+        }
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::{
+        import::{Importer, Record},
+        pocket::PocketImporter,
+    };
+
+    #[test]
+    fn parse_csv_export() {
+        let csv = "\
+title,url,time_added,tags,status
+\"Rust, the book\",https://doc.rust-lang.org/book/,1700000000,rust|books,unread
+,https://example.com/archived,1600000000,,archive
+";
+        assert_eq!(
+            vec![
+                Record {
+                    title: String::from("Rust, the book"),
+                    url: String::from("https://doc.rust-lang.org/book/"),
+                    added_at: DateTime::from_timestamp(1700000000, 0),
+                    tags: vec![String::from("rust"), String::from("books")],
+                    ..Default::default()
+                },
+                Record {
+                    url: String::from("https://example.com/archived"),
+                    added_at: DateTime::from_timestamp(1600000000, 0),
+                    read_at: DateTime::from_timestamp(1600000000, 0),
+                    ..Default::default()
+                },
+            ],
+            PocketImporter.parse(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_html_export() {
+        let html = r#"<!DOCTYPE html>
+<html>
+<head><title>Pocket Export</title></head>
+<body>
+<h1>Unread</h1>
+<ul>
+<li><a href="https://example.com/unread" time_added="1700000000" tags="rust,databases">Unread &amp; new</a></li>
+</ul>
+
+<h1>Read Archive</h1>
+<ul>
+<li><a href="https://example.com/read" time_added="1600000000" tags="">Read</a></li>
+</ul>
+</body>
+</html>"#;
+        assert_eq!(
+            vec![
+                Record {
+                    title: String::from("Unread & new"),
+                    url: String::from("https://example.com/unread"),
+                    added_at: DateTime::from_timestamp(1700000000, 0),
+                    tags: vec![String::from("rust"), String::from("databases")],
+                    ..Default::default()
+                },
+                Record {
+                    title: String::from("Read"),
+                    url: String::from("https://example.com/read"),
+                    added_at: DateTime::from_timestamp(1600000000, 0),
+                    read_at: DateTime::from_timestamp(1600000000, 0),
+                    ..Default::default()
+                },
+            ],
+            PocketImporter.parse(html).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_csv_without_url_column_fails() {
+        assert!(
+            PocketImporter
+                .parse("title,time_added\nA,1700000000\n")
+                .is_err()
+        );
+    }
+}
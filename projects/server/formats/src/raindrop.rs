@@ -0,0 +1,146 @@
+//! Raindrop.io exports csv files with the columns `id, title, note,
+//! excerpt, url, folder, tags, created, cover, highlights, favorite`. Its
+//! html export is a Netscape bookmark file.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    import::{ImportError, Importer, Record, csv_rows, is_html, split_tags},
+    netscape,
+};
+
+pub struct RaindropImporter;
+
+impl Importer for RaindropImporter {
+    fn name(&self) -> &'static str {
+        "Raindrop.io"
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<Record>, ImportError> {
+        if is_html(contents) {
+            return Ok(netscape::parse(contents)
+                .into_iter()
+                .map(Record::from)
+                .collect());
+        }
+
+        let rows = csv_rows::<RaindropRow>(contents)?;
+        Ok(rows.into_iter().map(Record::from).collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct RaindropRow {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    note: String,
+    #[serde(default)]
+    excerpt: String,
+    url: String,
+    #[serde(default)]
+    folder: String,
+    #[serde(default)]
+    tags: String,
+    #[serde(default)]
+    created: String,
+}
+
+/// The folder links end up in when they aren't sorted into another one.
+const UNSORTED_FOLDER: &str = "Unsorted";
+
+impl From<RaindropRow> for Record {
+    fn from(row: RaindropRow) -> Self {
+        let mut tags = split_tags(&row.tags, ',');
+        let folder = row.folder.trim();
+        if !folder.is_empty() && folder != UNSORTED_FOLDER {
+            // nested folders are exported as `Parent / Child`
+            tags.splice(0..0, split_tags(folder, '/'));
+        }
+
+        let description = [row.note, row.excerpt]
+            .into_iter()
+            .find(|d| !d.trim().is_empty());
+
+        Record {
+            title: row.title,
+            url: row.url,
+            added_at: DateTime::parse_from_rfc3339(row.created.trim())
+                .ok()
+                .map(|created| created.with_timezone(&Utc)),
+            // Raindrop has no read state
+            read_at: None,
+            tags,
+            description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::{
+        import::{Importer, Record},
+        raindrop::RaindropImporter,
+    };
+
+    #[test]
+    fn parse_csv_export() {
+        let csv = "\
+id,title,note,excerpt,url,folder,tags,created,cover,highlights,favorite
+1,Rust,My note,An excerpt,https://www.rust-lang.org/,Dev / Languages,\"rust, programming\",2023-11-14T22:13:20.000Z,,,false
+2,Unsorted link,,Only excerpt,https://example.com/,Unsorted,,not a date,,,true
+";
+        assert_eq!(
+            vec![
+                Record {
+                    title: String::from("Rust"),
+                    url: String::from("https://www.rust-lang.org/"),
+                    added_at: DateTime::from_timestamp(1700000000, 0),
+                    tags: vec![
+                        String::from("Dev"),
+                        String::from("Languages"),
+                        String::from("rust"),
+                        String::from("programming"),
+                    ],
+                    description: Some(String::from("My note")),
+                    ..Default::default()
+                },
+                Record {
+                    title: String::from("Unsorted link"),
+                    url: String::from("https://example.com/"),
+                    description: Some(String::from("Only excerpt")),
+                    ..Default::default()
+                },
+            ],
+            RaindropImporter.parse(csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_html_export() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<DL><p>
+<DT><H3>Reading</H3>
+<DL><p>
+<DT><A HREF="https://example.com/" ADD_DATE="1700000000" TAGS="a,b">Example</A>
+</DL><p>
+</DL><p>"#;
+        assert_eq!(
+            vec![Record {
+                title: String::from("Example"),
+                url: String::from("https://example.com/"),
+                added_at: DateTime::from_timestamp(1700000000, 0),
+                tags: vec![
+                    String::from("Reading"),
+                    String::from("a"),
+                    String::from("b")
+                ],
+                ..Default::default()
+            }],
+            RaindropImporter.parse(html).unwrap()
+        );
+    }
+}
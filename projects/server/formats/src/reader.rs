@@ -0,0 +1,426 @@
+//! The readable content of an article page, like the reader modes of
+//! browsers. The element holding most of the page's paragraphs is kept and
+//! written out again with only an allowlist of tags and attributes, so the
+//! stored html can be shown in the app as it is.
+
+use std::collections::HashMap;
+
+use domain::reader::ArticleContent;
+use scraper::{ElementRef, Html, Node};
+use url::Url;
+
+use crate::html::escape;
+
+/// Pages with less text than this have no article worth reading
+const MIN_TEXT_LENGTH: usize = 250;
+/// Shorter paragraphs, like captions or buttons, don't count
+const MIN_PARAGRAPH_LENGTH: usize = 25;
+
+/// Tags that are written out, all other elements are replaced by their
+/// content.
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "dd",
+    "del",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "small",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Elements that are dropped together with their content.
+const DROPPED_TAGS: &[&str] = &[
+    "aside", "audio", "button", "canvas", "embed", "footer", "form", "iframe", "input", "nav",
+    "noscript", "object", "script", "select", "style", "svg", "template", "textarea", "video",
+];
+
+const VOID_TAGS: &[&str] = &["br", "hr", "img"];
+
+/// Tags that end a paragraph of the plain text.
+const BLOCK_TAGS: &[&str] = &[
+    "article",
+    "blockquote",
+    "dd",
+    "div",
+    "dt",
+    "figcaption",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "p",
+    "pre",
+    "section",
+    "tr",
+];
+
+/// Parts of class names and ids that hint at the content of a page.
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "post", "story", "text",
+];
+
+/// Parts of class names and ids of everything around the content.
+const NEGATIVE_HINTS: &[&str] = &[
+    "ad-", "banner", "comment", "footer", "menu", "nav", "popup", "promo", "related", "share",
+    "sidebar", "social", "sponsor", "widget",
+];
+
+/// Finds the article on the page. Links and images are made absolute
+/// against `base_url`, links and images that aren't http(s) are dropped.
+/// `None` if the page has no article, like landing or index pages.
+pub fn extract(html: &str, base_url: &Url) -> Option<ArticleContent> {
+    let document = Html::parse_document(html);
+    let content = best_candidate(&document)?;
+
+    let mut writer = Writer {
+        base_url,
+        html: String::new(),
+        text: String::new(),
+        paragraph: String::new(),
+    };
+    writer.children(content);
+    writer.end_paragraph();
+
+    (writer.text.chars().count() >= MIN_TEXT_LENGTH).then_some(ArticleContent {
+        html: writer.html,
+        text: writer.text,
+    })
+}
+
+/// Scores the parents of paragraphs by the amount of text in them, the
+/// element with the highest score and few links holds the article.
+fn best_candidate(document: &Html) -> Option<ElementRef<'_>> {
+    let mut scores = HashMap::new();
+
+    for paragraph in document.root_element().descendent_elements() {
+        if !matches!(paragraph.value().name(), "p" | "pre" | "td" | "blockquote") {
+            continue;
+        }
+        let length = text_length(paragraph);
+        if length < MIN_PARAGRAPH_LENGTH {
+            continue;
+        }
+
+        let commas = paragraph
+            .text()
+            .map(|t| t.matches(',').count())
+            .sum::<usize>();
+        let score = 1.0 + commas as f64 + (length as f64 / 100.0).min(3.0);
+
+        let parent = paragraph.parent().and_then(ElementRef::wrap);
+        let grandparent = parent.and_then(|p| p.parent()).and_then(ElementRef::wrap);
+        for (ancestor, share) in [(parent, 1.0), (grandparent, 0.5)] {
+            if let Some(ancestor) = ancestor {
+                let entry = scores
+                    .entry(ancestor.id())
+                    .or_insert((ancestor, initial_score(ancestor)));
+                entry.1 += score * share;
+            }
+        }
+    }
+
+    scores
+        .into_values()
+        .filter(|(candidate, _)| !is_clutter(*candidate))
+        .map(|(candidate, score)| (candidate, score * (1.0 - link_density(candidate))))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(candidate, _)| candidate)
+}
+
+fn initial_score(element: ElementRef) -> f64 {
+    let tag_score = match element.value().name() {
+        "article" | "main" => 10.0,
+        "div" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "body" | "html" => -5.0,
+        _ => 0.0,
+    };
+    tag_score + hint_score(element)
+}
+
+fn hint_score(element: ElementRef) -> f64 {
+    let names = element_names(element);
+    let mut score = 0.0;
+    if POSITIVE_HINTS.iter().any(|hint| names.contains(hint)) {
+        score += 25.0;
+    }
+    if NEGATIVE_HINTS.iter().any(|hint| names.contains(hint)) {
+        score -= 25.0;
+    }
+    score
+}
+
+/// Whether the class names or the id say the element isn't content.
+fn is_clutter(element: ElementRef) -> bool {
+    let names = element_names(element);
+    NEGATIVE_HINTS.iter().any(|hint| names.contains(hint))
+        && !POSITIVE_HINTS.iter().any(|hint| names.contains(hint))
+}
+
+/// The lowercase class names and id of the element.
+fn element_names(element: ElementRef) -> String {
+    let value = element.value();
+    let mut names = value.classes().collect::<Vec<_>>().join(" ");
+    if let Some(id) = value.id() {
+        names.push(' ');
+        names.push_str(id);
+    }
+    names.to_lowercase()
+}
+
+fn text_length(element: ElementRef) -> usize {
+    element
+        .text()
+        .flat_map(str::split_whitespace)
+        .map(|word| word.chars().count() + 1)
+        .sum()
+}
+
+/// How much of the text of the element is inside of links.
+fn link_density(element: ElementRef) -> f64 {
+    let length = text_length(element);
+    if length == 0 {
+        return 1.0;
+    }
+    let link_length = element
+        .descendent_elements()
+        .filter(|e| e.value().name() == "a")
+        .map(text_length)
+        .sum::<usize>();
+    link_length as f64 / length as f64
+}
+
+/// Writes the allowed parts of the content as html and as plain text with
+/// one paragraph per line.
+struct Writer<'a> {
+    base_url: &'a Url,
+    html: String,
+    text: String,
+    /// text of the current paragraph, whitespace isn't collapsed yet
+    paragraph: String,
+}
+
+impl Writer<'_> {
+    fn children(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => {
+                    self.html.push_str(&escape(text));
+                    self.paragraph.push_str(text);
+                }
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        if DROPPED_TAGS.contains(&name) || is_clutter(element) {
+            return;
+        }
+        let is_block = BLOCK_TAGS.contains(&name);
+        if is_block {
+            self.end_paragraph();
+        }
+        if name == "br" {
+            self.paragraph.push(' ');
+        }
+
+        if !ALLOWED_TAGS.contains(&name) {
+            self.children(element);
+        } else if let Some(attributes) = self.attributes(element) {
+            self.html.push_str(&format!("<{name}{attributes}>"));
+            if !VOID_TAGS.contains(&name) {
+                self.children(element);
+                self.html.push_str(&format!("</{name}>"));
+            }
+        }
+
+        if is_block {
+            self.end_paragraph();
+        }
+    }
+
+    /// The allowed attributes of the element, `None` if the element can't
+    /// be written without the attributes it misses, like an image without
+    /// a usable source.
+    fn attributes(&self, element: ElementRef) -> Option<String> {
+        let value = element.value();
+        let mut attributes = vec![];
+
+        match value.name() {
+            "a" => {
+                if let Some(href) = value.attr("href").and_then(|href| self.absolute(href)) {
+                    attributes.push(("href", href));
+                }
+            }
+            "img" => {
+                let src = value.attr("src").or(value.attr("data-src"))?;
+                attributes.push(("src", self.absolute(src)?));
+                if let Some(alt) = value.attr("alt") {
+                    attributes.push(("alt", alt.to_string()));
+                }
+            }
+            "td" | "th" => {
+                for span in ["colspan", "rowspan"] {
+                    if let Some(count) = value.attr(span).filter(|c| c.parse::<u8>().is_ok()) {
+                        attributes.push((span, count.to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+        if let Some(title) = value.attr("title") {
+            attributes.push(("title", title.to_string()));
+        }
+
+        Some(
+            attributes
+                .into_iter()
+                .map(|(name, value)| format!(" {name}=\"{}\"", escape(&value)))
+                .collect(),
+        )
+    }
+
+    /// The url made absolute, `None` for anything but http(s) urls.
+    fn absolute(&self, url: &str) -> Option<String> {
+        let url = self.base_url.join(url.trim()).ok()?;
+        matches!(url.scheme(), "http" | "https").then(|| String::from(url))
+    }
+
+    fn end_paragraph(&mut self) {
+        let paragraph = self
+            .paragraph
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !paragraph.is_empty() {
+            if !self.text.is_empty() {
+                self.text.push_str("\n\n");
+            }
+            self.text.push_str(&paragraph);
+        }
+        self.paragraph.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::reader::extract;
+
+    fn base_url() -> Url {
+        Url::parse("https://blog.example.com/posts/reader-modes").unwrap()
+    }
+
+    #[test]
+    fn keeps_the_post_of_a_cluttered_blog() {
+        let html = include_str!("../fixtures/reader/blog.html");
+        let content = extract(html, &base_url()).unwrap();
+
+        assert!(
+            content
+                .text
+                .starts_with("How reader modes work\n\nBrowsers")
+        );
+        assert!(content.text.contains("scored by the amount of text"));
+        for clutter in ["Subscribe", "Archive", "Great post", "Share on", "alert"] {
+            assert!(!content.text.contains(clutter), "{clutter} was kept");
+            assert!(!content.html.contains(clutter), "{clutter} was kept");
+        }
+    }
+
+    #[test]
+    fn only_allowed_tags_and_attributes_are_kept() {
+        let html = include_str!("../fixtures/reader/blog.html");
+        let content = extract(html, &base_url()).unwrap();
+
+        assert!(content.html.contains(
+            r#"<img src="https://blog.example.com/images/scores.png" alt="Scores of elements">"#
+        ));
+        assert!(
+            content
+                .html
+                .contains(r#"<a href="https://blog.example.com/posts/scoring">"#)
+        );
+        assert!(content.html.contains("<pre><code>score = 1 + commas"));
+        for unsafe_html in [
+            "<script",
+            "onclick",
+            "javascript:",
+            "style=",
+            "class=",
+            "<div",
+            "<iframe",
+        ] {
+            assert!(
+                !content.html.contains(unsafe_html),
+                "{unsafe_html} was kept"
+            );
+        }
+        // the link to a javascript: url is kept as text
+        assert!(content.html.contains("<a>this demo</a>"));
+    }
+
+    #[test]
+    fn finds_semantic_articles_between_other_text() {
+        let html = include_str!("../fixtures/reader/news.html");
+        let content = extract(html, &base_url()).unwrap();
+
+        assert!(content.text.starts_with("City opens new library"));
+        assert!(content.text.contains("Opening hours"));
+        assert!(content.html.contains(r#"<td colspan="2">"#));
+        assert!(!content.text.contains("Most read"));
+        assert!(!content.text.contains("Cookie"));
+    }
+
+    #[test]
+    fn pages_without_articles_have_no_content() {
+        let html = include_str!("../fixtures/reader/landing.html");
+        assert_eq!(None, extract(html, &base_url()));
+    }
+}
@@ -0,0 +1,433 @@
+//! Self-contained copies of web pages for the archive. Stylesheets are
+//! inlined, images and fonts are replaced by data urls and scripts are
+//! dropped, so the copy still works when the original page is gone.
+
+use std::collections::{HashMap, HashSet};
+
+use scraper::{ElementRef, Html, Node};
+use url::Url;
+
+use crate::html::escape;
+
+/// Elements that are dropped together with their content, the copy is
+/// static and must not load anything from other sites.
+const DROPPED_TAGS: &[&str] = &[
+    "audio", "base", "embed", "iframe", "noembed", "noframes", "noscript", "object", "script",
+    "source", "template", "track", "video",
+];
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Attributes holding urls that are made absolute.
+const URL_ATTRIBUTES: &[&str] = &["action", "href", "poster", "src"];
+
+/// Attributes that would load something or run code and are dropped.
+const DROPPED_ATTRIBUTES: &[&str] = &["crossorigin", "integrity", "nonce", "ping", "srcset"];
+
+/// What a page needs to be shown, as absolute urls in the order they appear.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Resources {
+    pub stylesheets: Vec<String>,
+    /// Images, icons and the files referenced by inline styles
+    pub files: Vec<String>,
+}
+
+/// The downloaded resources of a page, by their absolute url.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Inlined {
+    /// The text of the stylesheets
+    pub stylesheets: HashMap<String, String>,
+    /// `data:` urls of images, icons and fonts
+    pub files: HashMap<String, String>,
+}
+
+/// The stylesheets and files the page references.
+pub fn resources(html: &str, base_url: &Url) -> Resources {
+    let document = Html::parse_document(html);
+    let mut resources = Resources::default();
+
+    for element in document.root_element().descendent_elements() {
+        let value = element.value();
+        match value.name() {
+            "link" => {
+                let Some(href) = value.attr("href").and_then(|href| absolute(base_url, href))
+                else {
+                    continue;
+                };
+                match link_kind(element) {
+                    Some(LinkKind::Stylesheet) => resources.stylesheets.push(href),
+                    Some(LinkKind::Icon) => resources.files.push(href),
+                    None => {}
+                }
+            }
+            "img" => {
+                if let Some(src) = image_source(element).and_then(|src| absolute(base_url, src)) {
+                    resources.files.push(src);
+                }
+            }
+            "style" => {
+                let css = element.text().collect::<String>();
+                resources.files.extend(css_urls(&css, base_url));
+            }
+            _ => {}
+        }
+    }
+
+    dedup(&mut resources.stylesheets);
+    dedup(&mut resources.files);
+    resources
+}
+
+/// The absolute urls of the files, like images and fonts, the stylesheet
+/// references. Imports aren't followed.
+pub fn css_urls(css: &str, css_url: &Url) -> Vec<String> {
+    let mut urls = vec![];
+    replace_css_urls(css, |url| {
+        if let Some(url) = absolute(css_url, url) {
+            urls.push(url);
+        }
+        None
+    });
+    dedup(&mut urls);
+    urls
+}
+
+/// Writes the page again with the stylesheets and files it references
+/// inlined. Scripts, event handlers and embedded content are dropped and
+/// links are made absolute, resources that weren't downloaded are left out.
+pub fn inline(html: &str, page_url: &Url, inlined: &Inlined) -> String {
+    let document = Html::parse_document(html);
+    let mut writer = Writer {
+        page_url,
+        inlined,
+        html: String::from("<!DOCTYPE html>\n"),
+    };
+    writer.html.push_str(&format!(
+        "<!-- archived copy of {} -->\n",
+        page_url.as_str().replace("--", "%2D%2D")
+    ));
+    writer.element(document.root_element());
+    writer.html
+}
+
+enum LinkKind {
+    Stylesheet,
+    Icon,
+}
+
+fn link_kind(link: ElementRef) -> Option<LinkKind> {
+    let rel = link.value().attr("rel")?.to_ascii_lowercase();
+    let rels = rel.split_whitespace().collect::<Vec<_>>();
+    if rels.contains(&"stylesheet") && !rels.contains(&"alternate") {
+        Some(LinkKind::Stylesheet)
+    } else if rels.contains(&"icon") {
+        Some(LinkKind::Icon)
+    } else {
+        None
+    }
+}
+
+/// Lazy loaded images often only have a placeholder as `src`.
+fn image_source<'a>(image: ElementRef<'a>) -> Option<&'a str> {
+    let value = image.value();
+    value
+        .attr("data-src")
+        .or(value.attr("src"))
+        .filter(|src| !src.trim().is_empty())
+}
+
+/// The url made absolute, `None` for anything but http(s) urls.
+fn absolute(base_url: &Url, url: &str) -> Option<String> {
+    let url = base_url.join(url.trim()).ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| String::from(url))
+}
+
+fn dedup(urls: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    urls.retain(|url| seen.insert(url.clone()));
+}
+
+/// Calls `replace` with every `url(...)` of the css that isn't a data url,
+/// the url is replaced if it returns a replacement.
+fn replace_css_urls(css: &str, mut replace: impl FnMut(&str) -> Option<String>) -> String {
+    let mut replaced = String::with_capacity(css.len());
+    let mut rest = css;
+
+    while let Some(start) = rest.find("url(") {
+        let Some(len) = rest[start..].find(')') else {
+            break;
+        };
+        let argument = &rest[start + 4..start + len];
+        let url = argument.trim().trim_matches(['"', '\'']).trim();
+
+        replaced.push_str(&rest[..start]);
+        match (url.starts_with("data:"), replace(url)) {
+            (false, Some(replacement)) => replaced.push_str(&format!("url(\"{replacement}\")")),
+            _ => replaced.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+struct Writer<'a> {
+    page_url: &'a Url,
+    inlined: &'a Inlined,
+    html: String,
+}
+
+impl Writer<'_> {
+    fn children(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.html.push_str(&escape(text)),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: ElementRef) {
+        let value = element.value();
+        let name = value.name();
+        if DROPPED_TAGS.contains(&name) {
+            return;
+        }
+
+        match name {
+            "link" => return self.link(element),
+            "style" => {
+                let css = element.text().collect::<String>();
+                return self.style(&css, self.page_url);
+            }
+            // the copy is written as utf-8 and must not redirect
+            "meta" if value.attr("charset").is_some() || value.attr("http-equiv").is_some() => {
+                return;
+            }
+            _ => {}
+        }
+
+        self.html.push('<');
+        self.html.push_str(name);
+        for (attribute, text) in value.attrs() {
+            if let Some(text) = self.attribute(element, attribute, text) {
+                self.html
+                    .push_str(&format!(" {attribute}=\"{}\"", escape(&text)));
+            }
+        }
+        self.html.push('>');
+        if name == "head" {
+            self.html.push_str("<meta charset=\"utf-8\">");
+        }
+
+        if !VOID_TAGS.contains(&name) {
+            self.children(element);
+            self.html.push_str(&format!("</{name}>"));
+        }
+    }
+
+    /// The value the attribute is written with, `None` drops it.
+    fn attribute(&self, element: ElementRef, attribute: &str, text: &str) -> Option<String> {
+        let attribute = attribute.to_ascii_lowercase();
+        if attribute.starts_with("on") || DROPPED_ATTRIBUTES.contains(&attribute.as_str()) {
+            return None;
+        }
+
+        let is_image = element.value().name() == "img";
+        match attribute.as_str() {
+            // lazy loaded images are written with their real source as src
+            "data-src" if is_image => None,
+            // images that weren't downloaded are written without a source
+            "src" if is_image => {
+                let source = image_source(element).and_then(|src| absolute(self.page_url, src))?;
+                self.inlined.files.get(&source).cloned()
+            }
+            "style" => Some(self.css(text, self.page_url)),
+            attribute if URL_ATTRIBUTES.contains(&attribute) => absolute(self.page_url, text),
+            _ => Some(text.to_string()),
+        }
+    }
+
+    /// Stylesheets are written as style elements, icons as data urls.
+    fn link(&mut self, link: ElementRef) {
+        let Some(href) = link
+            .value()
+            .attr("href")
+            .and_then(|href| absolute(self.page_url, href))
+        else {
+            return;
+        };
+
+        match link_kind(link) {
+            Some(LinkKind::Stylesheet) => {
+                if let (Some(css), Ok(css_url)) =
+                    (self.inlined.stylesheets.get(&href), Url::parse(&href))
+                {
+                    self.style(css, &css_url);
+                }
+            }
+            Some(LinkKind::Icon) => {
+                if let Some(data) = self.inlined.files.get(&href) {
+                    self.html
+                        .push_str(&format!("<link rel=\"icon\" href=\"{}\">", escape(data)));
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn style(&mut self, css: &str, css_url: &Url) {
+        let css = self.css(css, css_url);
+        // the css can't end the style element early
+        let css = css.replace("</", "<\\/");
+        self.html.push_str(&format!("<style>{css}</style>"));
+    }
+
+    /// The css with the urls of downloaded files replaced by data urls and
+    /// all other urls made absolute.
+    fn css(&self, css: &str, css_url: &Url) -> String {
+        replace_css_urls(css, |url| {
+            let url = absolute(css_url, url)?;
+            Some(self.inlined.files.get(&url).cloned().unwrap_or(url))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use url::Url;
+
+    use crate::snapshot::{Inlined, Resources, css_urls, inline, resources};
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+        <html>
+        <head>
+            <meta charset="iso-8859-1">
+            <meta http-equiv="refresh" content="0; url=https://spam.example.com">
+            <title>Archived &amp; kept</title>
+            <link rel="stylesheet" href="/css/site.css" integrity="sha384-x">
+            <link rel="alternate stylesheet" href="/css/dark.css">
+            <link rel="icon" href="/favicon.png">
+            <style>header { background: url('/img/header.jpg'); }</style>
+            <script src="/js/app.js"></script>
+        </head>
+        <body onload="track()">
+            <a href="/about" onclick="track()">About</a>
+            <a href="javascript:void(0)">Menu</a>
+            <img src="/img/photo.png" srcset="/img/photo-2x.png 2x" alt="Photo">
+            <img src="data:image/gif;base64,R0lGOD" data-src="/img/lazy.png" alt="Lazy">
+            <img src="/img/missing.png" alt="Missing">
+            <p style="background: url(dots.svg)">Text with <b>markup</b> &lt;kept&gt;</p>
+            <iframe src="https://ads.example.com"></iframe>
+            <noscript><img src="/pixel.gif"></noscript>
+        </body>
+        </html>"#;
+
+    fn page_url() -> Url {
+        Url::parse("https://blog.example.com/posts/archive").unwrap()
+    }
+
+    #[test]
+    fn finds_stylesheets_and_files() {
+        assert_eq!(
+            Resources {
+                stylesheets: vec![String::from("https://blog.example.com/css/site.css")],
+                files: vec![
+                    String::from("https://blog.example.com/favicon.png"),
+                    String::from("https://blog.example.com/img/header.jpg"),
+                    String::from("https://blog.example.com/img/photo.png"),
+                    String::from("https://blog.example.com/img/lazy.png"),
+                    String::from("https://blog.example.com/img/missing.png"),
+                ],
+            },
+            resources(PAGE, &page_url())
+        );
+    }
+
+    #[test]
+    fn finds_files_of_stylesheets() {
+        let css = r#"
+            @font-face { src: url("../fonts/serif.woff2") format("woff2"); }
+            body { background: url(data:image/png;base64,iVBOR) }
+            .logo { background-image: url( 'https://cdn.example.com/logo.svg' ) }
+        "#;
+        let css_url = Url::parse("https://blog.example.com/css/site.css").unwrap();
+
+        assert_eq!(
+            vec![
+                String::from("https://blog.example.com/fonts/serif.woff2"),
+                String::from("https://cdn.example.com/logo.svg"),
+            ],
+            css_urls(css, &css_url)
+        );
+    }
+
+    #[test]
+    fn inlines_downloaded_resources() {
+        let inlined = Inlined {
+            stylesheets: HashMap::from([(
+                String::from("https://blog.example.com/css/site.css"),
+                String::from("body { font: url(../fonts/serif.woff2) } </style><script>"),
+            )]),
+            files: HashMap::from([
+                (
+                    String::from("https://blog.example.com/fonts/serif.woff2"),
+                    String::from("data:font/woff2;base64,d09G"),
+                ),
+                (
+                    String::from("https://blog.example.com/img/photo.png"),
+                    String::from("data:image/png;base64,iVBOR"),
+                ),
+                (
+                    String::from("https://blog.example.com/img/lazy.png"),
+                    String::from("data:image/png;base64,TEFaWQ"),
+                ),
+            ]),
+        };
+
+        let html = inline(PAGE, &page_url(), &inlined);
+        assert!(html.starts_with(
+            "<!DOCTYPE html>\n<!-- archived copy of https://blog.example.com/posts/archive -->"
+        ));
+        assert!(html.contains(r#"<head><meta charset="utf-8">"#));
+        assert!(html.contains("<title>Archived &amp; kept</title>"));
+        assert!(html.contains(
+            r#"<style>body { font: url("data:font/woff2;base64,d09G") } <\/style><script></style>"#
+        ));
+        assert!(html.contains(r#"<img alt="Photo" src="data:image/png;base64,iVBOR">"#));
+        assert!(html.contains(r#"<img alt="Lazy" src="data:image/png;base64,TEFaWQ">"#));
+        assert!(html.contains(r#"<img alt="Missing">"#));
+        assert!(html.contains(r#"<a href="https://blog.example.com/about">About</a>"#));
+        assert!(html.contains("<a>Menu</a>"));
+        assert!(html.contains(
+            r#"<p style="background: url(&quot;https://blog.example.com/posts/dots.svg&quot;)">"#
+        ));
+        assert!(html.contains("Text with <b>markup</b> &lt;kept&gt;"));
+
+        for dropped in [
+            "<script src",
+            "onclick",
+            "onload",
+            "srcset",
+            "integrity",
+            "iframe",
+            "pixel.gif",
+            "http-equiv",
+            "iso-8859-1",
+            "dark.css",
+            "favicon.png",
+        ] {
+            assert!(!html.contains(dropped), "{dropped} was kept");
+        }
+    }
+}
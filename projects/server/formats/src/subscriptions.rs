@@ -0,0 +1,427 @@
+//! Reads the RSS and Atom feeds the collection is subscribed to, every entry
+//! becomes a [`Record`] that is inserted like an imported link.
+//!
+//! Only what the articles store is read, everything else in the feed,
+//! including elements of unknown namespaces, is skipped.
+
+use std::{error::Error, fmt::Display};
+
+use chrono::{DateTime, Utc};
+use quick_xml::{
+    Reader,
+    events::{BytesStart, Event},
+};
+
+use crate::{html::text_content, import::Record};
+
+/// The entries of a feed in the order of the feed, usually newest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeedContents {
+    pub title: Option<String>,
+    pub entries: Vec<Record>,
+}
+
+#[derive(Debug)]
+pub enum FeedError {
+    Xml(quick_xml::Error),
+    /// The document is valid xml but neither an RSS nor an Atom feed
+    NotAFeed,
+}
+
+impl Display for FeedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedError::Xml(err) => write!(f, "invalid feed: {err}"),
+            FeedError::NotAFeed => write!(f, "not an RSS or Atom feed"),
+        }
+    }
+}
+
+impl Error for FeedError {}
+
+impl From<quick_xml::Error> for FeedError {
+    fn from(value: quick_xml::Error) -> Self {
+        FeedError::Xml(value)
+    }
+}
+
+/// Parses RSS 0.9x, 1.0 and 2.0 as well as Atom feeds.
+pub fn parse(xml: &str) -> Result<FeedContents, FeedError> {
+    let mut reader = Reader::from_str(xml);
+    let mut contents = FeedContents::default();
+    // local names of the open elements, lowercase
+    let mut open: Vec<String> = vec![];
+    let mut entry: Option<Entry> = None;
+    // the element whose text is collected and how deep it is nested
+    let mut capture: Option<(String, usize)> = None;
+    let mut text = String::new();
+    let mut has_root = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let name = local_name(&element);
+                if open.is_empty() {
+                    if has_root || !FEED_ROOTS.contains(&name.as_str()) {
+                        return Err(FeedError::NotAFeed);
+                    }
+                    has_root = true;
+                }
+                open.push(name.clone());
+                if capture.is_some() {
+                    // markup of xhtml content, only its text is kept
+                    text.push(' ');
+                    continue;
+                }
+                match (&mut entry, name.as_str()) {
+                    (None, "item" | "entry") => entry = Some(Entry::default()),
+                    (Some(entry), _) => {
+                        entry.read_attributes(&name, &element);
+                        capture = Some((name, open.len()));
+                    }
+                    (None, "title") if is_feed_level(&open) => {
+                        capture = Some((name, open.len()));
+                    }
+                    _ => {}
+                }
+            }
+            Event::Empty(element) => {
+                let name = local_name(&element);
+                if open.is_empty() {
+                    // an empty feed like `<feed/>`
+                    return match FEED_ROOTS.contains(&name.as_str()) {
+                        true => Ok(contents),
+                        false => Err(FeedError::NotAFeed),
+                    };
+                }
+                if let (Some(entry), None) = (&mut entry, &capture) {
+                    entry.read_attributes(&name, &element);
+                }
+            }
+            Event::End(_) => {
+                let depth = open.len();
+                let name = open.pop().unwrap_or_default();
+                match &capture {
+                    Some((_, capture_depth)) if *capture_depth == depth => {
+                        let captured = std::mem::take(&mut text);
+                        capture = None;
+                        match &mut entry {
+                            Some(entry) => entry.read_text(&name, &captured),
+                            None => contents.title = Some(text_content(&captured)),
+                        }
+                    }
+                    Some(_) => text.push(' '),
+                    None => {
+                        if matches!(name.as_str(), "item" | "entry")
+                            && let Some(entry) = entry.take()
+                        {
+                            contents.entries.extend(entry.into_record());
+                        }
+                    }
+                }
+            }
+            Event::Text(content) if capture.is_some() => {
+                text.push_str(&content.decode().map_err(quick_xml::Error::from)?);
+            }
+            Event::CData(content) if capture.is_some() => {
+                text.push_str(&content.decode().map_err(quick_xml::Error::from)?);
+            }
+            Event::GeneralRef(reference) if capture.is_some() => {
+                // entities are decoded together with the rest of the text
+                let name = reference.decode().map_err(quick_xml::Error::from)?;
+                text.push_str(&format!("&{name};"));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    match has_root {
+        true => Ok(contents),
+        false => Err(FeedError::NotAFeed),
+    }
+}
+
+/// `rss` for RSS 0.9x and 2.0, `rdf` for RSS 1.0 and `feed` for Atom
+const FEED_ROOTS: [&str; 3] = ["rss", "rdf", "feed"];
+
+/// The title of the feed is directly in the `<channel>` of RSS or in the
+/// `<feed>` of Atom, other titles belong to images or text inputs.
+fn is_feed_level(open: &[String]) -> bool {
+    match open {
+        [_, parent, _] => parent == "channel",
+        [root, _] => root == "feed" || root == "rdf",
+        _ => false,
+    }
+}
+
+fn local_name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.local_name().as_ref()).to_lowercase()
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name.as_bytes())
+        .and_then(|a| a.unescape_value().ok())
+        .map(|value| value.trim().to_string())
+}
+
+/// The fields of an entry while it is read, RSS and Atom name the same
+/// fields differently.
+#[derive(Default)]
+struct Entry {
+    title: String,
+    link: Option<String>,
+    /// RSS guids are often the link of the entry
+    guid: Option<String>,
+    published: Option<DateTime<Utc>>,
+    updated: Option<DateTime<Utc>>,
+    summary: Option<String>,
+    content: Option<String>,
+    categories: Vec<String>,
+}
+
+impl Entry {
+    /// Atom puts the link and the categories into attributes.
+    fn read_attributes(&mut self, name: &str, element: &BytesStart) {
+        match name {
+            "link" => {
+                let rel = attribute(element, "rel");
+                if let (None, Some(href)) = (&self.link, attribute(element, "href"))
+                    && rel.is_none_or(|rel| rel == "alternate")
+                {
+                    self.link = Some(href);
+                }
+            }
+            "category" => self.categories.extend(attribute(element, "term")),
+            "guid" if attribute(element, "isPermaLink").as_deref() == Some("false") => {
+                // an empty string marks the guid as unusable
+                self.guid = Some(String::new());
+            }
+            _ => {}
+        }
+    }
+
+    fn read_text(&mut self, name: &str, text: &str) {
+        let text = text.trim();
+        match name {
+            "title" => self.title = text_content(text),
+            "link" if self.link.is_none() && !text.is_empty() => {
+                self.link = Some(text_content(text));
+            }
+            "guid" if self.guid.is_none() => self.guid = Some(text_content(text)),
+            "pubdate" | "published" | "issued" | "date" => self.published = parse_date(text),
+            "updated" | "modified" => self.updated = parse_date(text),
+            "description" | "summary" => self.summary = Some(html_text(text)),
+            "content" | "encoded" => self.content = Some(html_text(text)),
+            "category" if !text.is_empty() => self.categories.push(text_content(text)),
+            _ => {}
+        }
+    }
+
+    /// Entries without any link can't be collected and are dropped.
+    fn into_record(self) -> Option<Record> {
+        let url = self
+            .link
+            .or(self.guid.filter(|guid| guid.starts_with("http")))?;
+
+        Some(Record {
+            title: self.title,
+            url,
+            added_at: self.published.or(self.updated),
+            read_at: None,
+            tags: self.categories,
+            description: self
+                .summary
+                .or(self.content)
+                .filter(|d| !d.is_empty())
+                .map(|d| shorten(&d)),
+        })
+    }
+}
+
+/// Html in feeds is escaped, once to get the html and a second time for the
+/// entities inside the html.
+fn html_text(text: &str) -> String {
+    text_content(&text_content(text))
+}
+
+/// RSS dates are RFC 2822 while Atom and Dublin Core use RFC 3339.
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(text)
+        .or_else(|_| DateTime::parse_from_rfc3339(text))
+        .ok()
+        .map(|date| date.to_utc())
+}
+
+/// Whole posts in the description would dwarf everything else of the
+/// article.
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+fn shorten(description: &str) -> String {
+    match description.char_indices().nth(MAX_DESCRIPTION_CHARS) {
+        Some((end, _)) => format!("{}…", description[..end].trim_end()),
+        None => description.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::{
+        import::Record,
+        subscriptions::{FeedError, MAX_DESCRIPTION_CHARS, parse},
+    };
+
+    #[test]
+    fn parses_rss() {
+        let feed = parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+              <channel>
+                <title>Tom &amp; friends</title>
+                <link>https://blog.example.com</link>
+                <image><title>Logo</title><url>https://blog.example.com/logo.png</url></image>
+                <item>
+                  <title>Vec&lt;T&gt; explained</title>
+                  <link>https://blog.example.com/vec?a=1&amp;b=2</link>
+                  <pubDate>Tue, 14 Nov 2023 22:13:20 +0000</pubDate>
+                  <description>&lt;p&gt;All about &amp;lt;vectors&amp;gt;&lt;/p&gt;</description>
+                  <category>Rust</category>
+                  <category>std</category>
+                </item>
+                <item>
+                  <title><![CDATA[No link]]></title>
+                  <guid isPermaLink="false">post-2</guid>
+                </item>
+                <item>
+                  <guid>https://blog.example.com/guid-only</guid>
+                  <content:encoded><![CDATA[<p>Full <b>content</b></p>]]></content:encoded>
+                </item>
+              </channel>
+            </rss>"#,
+        )
+        .unwrap();
+
+        assert_eq!(Some(String::from("Tom & friends")), feed.title);
+        assert_eq!(
+            vec![
+                Record {
+                    title: String::from("Vec<T> explained"),
+                    url: String::from("https://blog.example.com/vec?a=1&b=2"),
+                    added_at: DateTime::from_timestamp(1700000000, 0),
+                    read_at: None,
+                    tags: vec![String::from("Rust"), String::from("std")],
+                    description: Some(String::from("All about <vectors>")),
+                },
+                Record {
+                    url: String::from("https://blog.example.com/guid-only"),
+                    description: Some(String::from("Full content")),
+                    ..Default::default()
+                },
+            ],
+            feed.entries
+        );
+    }
+
+    #[test]
+    fn parses_atom() {
+        let feed = parse(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+              <title type="text">Atom blog</title>
+              <link href="https://atom.example.com/" rel="alternate"/>
+              <entry>
+                <title>First post</title>
+                <link href="https://atom.example.com/first.atom" rel="self"/>
+                <link href="https://atom.example.com/first"/>
+                <updated>2023-11-15T00:00:00Z</updated>
+                <published>2023-11-14T22:13:20Z</published>
+                <summary>Short</summary>
+                <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml">Long</div></content>
+                <category term="rust"/>
+              </entry>
+              <entry>
+                <title>Only updated</title>
+                <link rel="alternate" href="https://atom.example.com/second"></link>
+                <updated>2023-11-14T22:13:20+01:00</updated>
+              </entry>
+            </feed>"#,
+        )
+        .unwrap();
+
+        assert_eq!(Some(String::from("Atom blog")), feed.title);
+        assert_eq!(
+            vec![
+                Record {
+                    title: String::from("First post"),
+                    url: String::from("https://atom.example.com/first"),
+                    added_at: DateTime::from_timestamp(1700000000, 0),
+                    read_at: None,
+                    tags: vec![String::from("rust")],
+                    description: Some(String::from("Short")),
+                },
+                Record {
+                    title: String::from("Only updated"),
+                    url: String::from("https://atom.example.com/second"),
+                    added_at: DateTime::from_timestamp(1700000000 - 3600, 0),
+                    ..Default::default()
+                },
+            ],
+            feed.entries
+        );
+    }
+
+    #[test]
+    fn parses_rss_1() {
+        let feed = parse(
+            r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                xmlns="http://purl.org/rss/1.0/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+              <channel><title>Old school</title></channel>
+              <item>
+                <title>Entry</title>
+                <link>https://old.example.com/entry</link>
+                <dc:date>2023-11-14T22:13:20Z</dc:date>
+              </item>
+            </rdf:RDF>"#,
+        )
+        .unwrap();
+
+        assert_eq!(Some(String::from("Old school")), feed.title);
+        assert_eq!(1, feed.entries.len());
+        assert_eq!(
+            DateTime::from_timestamp(1700000000, 0),
+            feed.entries[0].added_at
+        );
+    }
+
+    #[test]
+    fn long_descriptions_are_shortened() {
+        let feed = parse(&format!(
+            "<rss><channel><item><link>https://example.com</link>\
+            <description>{}</description></item></channel></rss>",
+            "a".repeat(MAX_DESCRIPTION_CHARS + 10)
+        ))
+        .unwrap();
+
+        let description = feed.entries[0].description.clone().unwrap();
+        assert_eq!(MAX_DESCRIPTION_CHARS + 1, description.chars().count());
+        assert!(description.ends_with('…'));
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert!(matches!(
+            parse("<html><body><p>hi</p></body></html>"),
+            Err(FeedError::NotAFeed)
+        ));
+        assert!(matches!(parse(""), Err(FeedError::NotAFeed)));
+        assert!(matches!(
+            parse("<rss><channel><title>a</channel></rss>"),
+            Err(FeedError::Xml(_))
+        ));
+    }
+}
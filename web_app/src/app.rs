@@ -6,9 +6,12 @@ use leptos_router::{
 };
 
 use crate::{
-    articles::{edit::EditArticles, list::ArticlesList, single::SingleArticle},
+    articles::{edit::EditArticles, list::ArticlesList, single::SingleArticle, trash::TrashPage},
     keycloak::{InitAuth, KeycloakInfo, LoginButton, Logout, ShowWhenAuthenticated},
+    maintenance::MaintenancePage,
     routes::FallbackRoute,
+    subscriptions::FeedsPage,
+    tag_rules::TagRulesPage,
     utils::{
         dialog::{DialogSignal, GlobalDialog},
         Button, CenterColumn,
@@ -25,6 +28,9 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                 <AutoReload options=options.clone() />
                 <HydrationScripts options/>
                 <MetaTags/>
+                <link rel="alternate" type="application/rss+xml" title="Articles" href="/feed.rss"/>
+                <link rel="alternate" type="application/atom+xml" title="Articles" href="/feed.atom"/>
+                <link rel="alternate" type="application/feed+json" title="Articles" href="/feed.json"/>
             </head>
             <body>
                 <App/>
@@ -64,6 +70,10 @@ pub fn App() -> impl IntoView {
                         <Route path=path!("/articles") view=HomePage />
                         <Route path=path!("/articles/:uuid") view=SingleArticle />
                         <Route path=path!("/edit") view=EditArticles />
+                        <Route path=path!("/trash") view=TrashPage />
+                        <Route path=path!("/feeds") view=FeedsPage />
+                        <Route path=path!("/maintenance") view=MaintenancePage />
+                        <Route path=path!("/tag-rules") view=TagRulesPage />
                     </Routes>
                 </InitAuth>
             </Router>
@@ -114,6 +124,18 @@ fn GlobalNavBar() -> impl IntoView {
                             <A href="/edit" on:click=move |_| nav_open.set(false)>
                                 "Add Articles"
                             </A>
+                            <A href="/feeds" on:click=move |_| nav_open.set(false)>
+                                "Feeds"
+                            </A>
+                            <A href="/tag-rules" on:click=move |_| nav_open.set(false)>
+                                "Tag Rules"
+                            </A>
+                            <A href="/trash" on:click=move |_| nav_open.set(false)>
+                                "Trash"
+                            </A>
+                            <A href="/maintenance" on:click=move |_| nav_open.set(false)>
+                                "Maintenance"
+                            </A>
                             <Logout />
                         </div>
                     </ShowWhenAuthenticated>
@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use database::{articles_query, snapshots_query};
+use tracing::error;
+use uuid::Uuid;
+use web_app::ServerState;
+
+use crate::caching::has_etag;
+
+/// The archived page is shown in a sandbox without scripts that can only
+/// load what is inlined, so it can't act as part of this site
+const CONTENT_SECURITY_POLICY: &str = "sandbox; default-src 'none'; img-src data:; \
+    style-src 'unsafe-inline' data:; font-src data:";
+
+/// Serves the latest snapshot of the article, `/articles/{uuid}/archive`.
+/// Trashed articles are hidden like everywhere else.
+pub async fn serve_archive(
+    State(state): State<ServerState>,
+    Path(uuid): Path<Uuid>,
+    headers: HeaderMap,
+) -> Response {
+    match articles_query::one(&state.db, uuid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            error!("loading article {uuid} failed: {err}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let snapshot = match snapshots_query::latest(&state.db, uuid).await {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => {
+            return (StatusCode::NOT_FOUND, "this article wasn't archived yet").into_response()
+        }
+        Err(err) => {
+            error!("loading the snapshot of {uuid} failed: {err}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let etag = format!("\"{}\"", snapshot.hash);
+    let security_headers = [
+        (header::CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY),
+        (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        (header::REFERRER_POLICY, "no-referrer"),
+    ];
+    if has_etag(&headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+
+    (
+        security_headers,
+        [
+            (
+                header::CONTENT_TYPE,
+                String::from("text/html; charset=utf-8"),
+            ),
+            (header::ETAG, etag),
+            // a newer snapshot can replace this one
+            (header::CACHE_CONTROL, String::from("no-cache")),
+        ],
+        snapshot.html,
+    )
+        .into_response()
+}
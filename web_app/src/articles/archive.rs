@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use domain::{articles::Article, jobs::EnrichmentStatus};
+use leptos::{prelude::*, server};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{articles::format_date, keycloak::AuthClient, utils::Button};
+
+/// Links to the latest snapshot of the article, nothing while there is none.
+#[component]
+pub fn ArchiveLink(uuid: Uuid) -> impl IntoView {
+    let taken_at = OnceResource::new(async move {
+        get_snapshot_taken_at(uuid).await.unwrap_or_else(|err| {
+            error!("failed to load the snapshot of {uuid}: {err}");
+            None
+        })
+    });
+
+    view! {
+        <Suspense>
+            {Suspend::new(async move { taken_at.await.map(|taken_at| view! {
+                // external, so the router doesn't take the archive for a page of the app
+                <a href=format!("/articles/{uuid}/archive") target="_blank" rel="external"
+                    class="text-blue-600">
+                    {format!("archived {}", format_date(&taken_at))}
+                </a>
+            })})}
+        </Suspense>
+    }
+}
+
+/// Queues a new snapshot of the article, e.g. because the page changed.
+#[component]
+pub fn ArchiveButton(article: RwSignal<Article>) -> impl IntoView {
+    let archive = Action::new(move |_: &()| {
+        let uuid = article.read_untracked().uuid;
+        async move {
+            match archive_article(uuid).await {
+                Ok(()) => article.update(|a| a.enrichment = Some(EnrichmentStatus::Enriching)),
+                Err(err) => error!("failed to archive {uuid}: {err}"),
+            }
+        }
+    });
+
+    view! {
+        <button disabled=move || archive.pending().get()
+            on:click=move |_| { archive.dispatch(()); }>
+            <Button>
+                "archive now"
+            </Button>
+        </button>
+    }
+}
+
+#[server(prefix = "/public/api")]
+async fn get_snapshot_taken_at(uuid: Uuid) -> Result<Option<DateTime<Utc>>, ServerFnError> {
+    use crate::ServerState;
+    use database::snapshots_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(snapshots_query::latest_taken_at(&state.db, uuid).await?)
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn archive_article(uuid: Uuid) -> Result<(), ServerFnError> {
+    use crate::ServerState;
+    use database::jobs_query;
+    use domain::jobs::JobKind;
+
+    let state = expect_context::<ServerState>();
+    Ok(jobs_query::enqueue(vec![uuid], &[JobKind::Snapshot], &state.db).await?)
+}
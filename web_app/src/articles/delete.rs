@@ -14,7 +14,7 @@ async fn delete_article(article_uuid: Uuid) -> Result<(), ServerFnError> {
     use database::articles_query;
 
     let state = expect_context::<ServerState>();
-    Ok(articles_query::delete(article_uuid, &state.db).await?)
+    Ok(articles_query::trash(article_uuid, &state.db).await?)
 }
 
 #[cfg(test)]
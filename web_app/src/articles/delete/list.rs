@@ -20,8 +20,8 @@ pub fn open_delete_dialog_action_list(
                 move || {
                     delete_action.dispatch(uuid);
                 },
-                "Delete Item?",
-                "Do you really want to delete this Item?",
+                "Move Item to Trash?",
+                "Do you really want to move this Item to the trash?",
             ));
         }
     })
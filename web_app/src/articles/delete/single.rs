@@ -67,8 +67,8 @@ impl DeleteDialogAction {
                         info!("close....");
                         is_open.set(false);
                     },
-                    "Delete Item?",
-                    "Do you really want to delete this Item?",
+                    "Move Item to Trash?",
+                    "Do you really want to move this Item to the trash?",
                 ));
             }
         });
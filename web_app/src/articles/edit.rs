@@ -1,8 +1,12 @@
-use domain::articles::Article;
+use domain::{
+    submission::{LineOutcome, SubmissionReport},
+    tags::parse_tag_list,
+};
 use leptos::{form::MultiActionForm, prelude::*, server::ServerMultiAction};
 use web_sys::HtmlTextAreaElement;
 
 use crate::{
+    articles::import::ImportFile,
     keycloak::{AuthClient, ExpectAuth},
     utils::{
         extensions::{MultiactionLastSubSignalExtensions, ServerMultiActionExtensions},
@@ -14,6 +18,7 @@ use crate::{
 pub fn EditArticles() -> impl IntoView {
     let add_articles = ServerMultiAction::<AddArticles>::new();
     let links = RwSignal::new(String::new());
+    let tags = RwSignal::new(String::new());
 
     let submission = add_articles.last_submission_signal();
     let is_pending = submission.pending();
@@ -22,8 +27,8 @@ pub fn EditArticles() -> impl IntoView {
     Effect::watch(
         state,
         move |val, _, _| {
-            if let Some(Ok(())) = val {
-                links.set(String::new())
+            if let Some(Ok(report)) = val {
+                links.set(report.failed_lines())
             }
         },
         false,
@@ -35,16 +40,65 @@ pub fn EditArticles() -> impl IntoView {
                 <MultiActionForm action=add_articles>
                     { move || match is_pending.get() {
                         true => CenteredLoader().into_any(),
-                        false => AddForm(AddFormProps { links}).into_any(),
+                        false => AddForm(AddFormProps { links, tags }).into_any(),
                     }}
                 </MultiActionForm>
+                {move || match state.get() {
+                    Some(Ok(report)) => Report(ReportProps { report }).into_any(),
+                    Some(Err(err)) => view! {
+                        <p class="p-2 text-red-700">{err.to_string()}</p>
+                    }.into_any(),
+                    None => ().into_any(),
+                }}
+                <ImportFile />
             </ExpectAuth>
         </CenterColumn>
     }
 }
 
+/// Shows what happened to every line of the last submission. Lines that
+/// could not be added are put back into the input so they can be fixed.
+#[component]
+pub(crate) fn Report(report: SubmissionReport) -> impl IntoView {
+    let inserted = report.count(|o| *o == LineOutcome::Inserted);
+    let duplicates = report.count(|o| *o == LineOutcome::Duplicate);
+    let failed = report.count(LineOutcome::is_failure);
+    let is_empty = report.lines.is_empty();
+
+    view! {
+        <Show when=move || !is_empty>
+            <div class="flex flex-col gap-1 p-2">
+                <h3 class="underline">
+                    {format!("{inserted} added, {duplicates} duplicates skipped, {failed} invalid")}
+                </h3>
+                <ul>
+                    {report.lines.clone()
+                        .into_iter()
+                        .map(|l| {
+                            let (class, outcome) = match l.outcome {
+                                LineOutcome::Inserted => ("text-green-700", String::from("added")),
+                                LineOutcome::Duplicate => ("text-gray-500", String::from("duplicate")),
+                                LineOutcome::InvalidUrl(err) => ("text-red-700", format!("invalid: {err}")),
+                                LineOutcome::NoUrl => ("text-gray-400", String::from("no link found")),
+                                LineOutcome::Blank => ("text-gray-400", String::from("blank, skipped")),
+                            };
+                            view! {
+                                <li class=format!("flex gap-2 wrap-break-word {class}")>
+                                    <span class="shrink-0">{format!("{}:", l.number)}</span>
+                                    <span class="grow">{l.line}</span>
+                                    <span class="shrink-0">{outcome}</span>
+                                </li>
+                            }
+                        })
+                        .collect_view()}
+                </ul>
+            </div>
+        </Show>
+    }
+}
+
 #[component]
-fn AddForm(links: RwSignal<String>) -> impl IntoView {
+fn AddForm(links: RwSignal<String>, tags: RwSignal<String>) -> impl IntoView {
     let textarea_ref = NodeRef::new();
 
     view! {
@@ -73,83 +127,44 @@ fn AddForm(links: RwSignal<String>) -> impl IntoView {
             <textarea name="file_contents"
                 bind:value=links
                 node_ref=textarea_ref
+                placeholder="Links, `title | url` lines, Markdown links or whole posts containing links"
                 class="field-sizing-content h-100 border-1 border-gray-200">
             </textarea>
+            <label class="underline" for="tags">
+                "Tags (comma separated)"
+            </label>
+            <input type="text" name="tags" id="tags"
+                bind:value=tags
+                placeholder="rust, databases, to-share"
+                class="border-1 border-gray-200 px-1" />
         </div>
     }
 }
 
+/// Links are inserted right away, only short links are resolved first. Links
+/// without a title are titled after their url until the background worker
+/// read the title of their page.
 #[server(
     client = AuthClient
 )]
-async fn add_articles(file_contents: String) -> Result<(), ServerFnError> {
-    use crate::ServerState;
-    use database::articles_query;
-
-    if file_contents.is_empty() {
-        return Ok(());
-    }
+async fn add_articles(
+    file_contents: String,
+    tags: String,
+) -> Result<SubmissionReport, ServerFnError> {
+    use crate::{jobs::insert_and_enrich, ServerState};
+    use domain::{articles::Article, submission::pasted_entries};
 
     let state = expect_context::<ServerState>();
-
-    let articles = file_contents
-        .lines()
-        .map(|line| {
-            let line = String::from(line);
-            let title = get_title_from_url(line.clone());
-            Article::from_parts(title, line)
+    let tags = parse_tag_list(&tags);
+    let entries = pasted_entries(&file_contents)
+        .into_iter()
+        .map(|(number, line, link)| {
+            let article = link.map(|link| {
+                Article::from_parts(link.title_or_from_url(), link.url).with_tags(tags.clone())
+            });
+            (number, line, article)
         })
         .collect();
 
-    Ok(articles_query::insert_many(articles, &state.db).await?)
-}
-
-#[cfg(any(feature = "ssr", test))]
-fn get_title_from_url(mut url: String) -> String {
-    let url = if url.starts_with("https://") {
-        let _ = url.drain(0..8);
-        url
-    } else if url.starts_with("http://") {
-        let _ = url.drain(0..7);
-        url
-    } else {
-        url
-    };
-    let mut parts = url.split('/');
-    let first = parts.next().unwrap().to_string();
-    let last = parts.rev().find(|p| !p.is_empty() && !p.eq(&first));
-
-    match last {
-        Some(last) => format!("{first} - {last}"),
-        None => first,
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn get_title_from_url_https_ending_slash() {
-        let url = "https://www.nasa.gov/centers-and-facilities/stennis/stennis-first-open-source-software/".into();
-        let res = get_title_from_url(url);
-        assert_eq!(
-            String::from("www.nasa.gov - stennis-first-open-source-software"),
-            res
-        );
-    }
-
-    #[test]
-    fn get_title_from_url_https() {
-        let url = "https://github.com/mrkline/modern-latex".into();
-        let res = get_title_from_url(url);
-        assert_eq!(String::from("github.com - modern-latex"), res);
-    }
-
-    #[test]
-    fn get_title_from_url_http() {
-        let url = "http://github.com/mrkline/modern-latex".into();
-        let res = get_title_from_url(url);
-        assert_eq!(String::from("github.com - modern-latex"), res);
-    }
+    Ok(insert_and_enrich(entries, &state.fetcher, &state.db).await?)
 }
@@ -0,0 +1,54 @@
+use domain::{articles::Article, jobs::EnrichmentStatus};
+use leptos::{prelude::*, server};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::keycloak::{AuthClient, ShowWhenAuthenticated};
+
+/// Shows whether the background worker is still enriching the article or
+/// gave up on it, failed enrichments can be retried.
+#[component]
+pub fn EnrichmentState(article: RwSignal<Article>) -> impl IntoView {
+    let retry = Action::new(move |_: &()| {
+        let uuid = article.read_untracked().uuid;
+        async move {
+            match retry_enrichment(uuid).await {
+                Ok(()) => article.update(|a| a.enrichment = Some(EnrichmentStatus::Enriching)),
+                Err(err) => error!("failed to retry enrichment of {uuid}: {err}"),
+            }
+        }
+    });
+
+    move || {
+        article.get().enrichment.map(|status| match status {
+            EnrichmentStatus::Enriching => view! {
+                <span class="text-gray-500">"enriching…"</span>
+            }
+            .into_any(),
+            EnrichmentStatus::Failed(error) => view! {
+                <span class="text-red-600" title=error>
+                    "enrichment failed"
+                    <ShowWhenAuthenticated>
+                        <button class="ml-2 underline"
+                            disabled=move || retry.pending().get()
+                            on:click=move |_| { retry.dispatch(()); }>
+                            "retry"
+                        </button>
+                    </ShowWhenAuthenticated>
+                </span>
+            }
+            .into_any(),
+        })
+    }
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn retry_enrichment(article_uuid: Uuid) -> Result<(), ServerFnError> {
+    use crate::ServerState;
+    use database::jobs_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(jobs_query::retry_failed(article_uuid, &state.db).await?)
+}
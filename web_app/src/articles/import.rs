@@ -0,0 +1,235 @@
+use domain::{articles::Article, submission::SubmissionReport};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use web_sys::HtmlInputElement;
+
+use crate::{
+    articles::{edit::Report, tags::TagList, ArticleDate},
+    keycloak::AuthClient,
+    utils::{Button, CenteredLoader},
+};
+
+/// The services whose export files can be imported
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ImportSource {
+    /// Netscape bookmark file, as exported by browsers
+    #[default]
+    Bookmarks,
+    Pocket,
+    Instapaper,
+    Raindrop,
+}
+
+impl ImportSource {
+    const ALL: [ImportSource; 4] = [
+        ImportSource::Bookmarks,
+        ImportSource::Pocket,
+        ImportSource::Instapaper,
+        ImportSource::Raindrop,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ImportSource::Bookmarks => "Browser bookmarks (bookmarks.html)",
+            ImportSource::Pocket => "Pocket (csv or html)",
+            ImportSource::Instapaper => "Instapaper (csv)",
+            ImportSource::Raindrop => "Raindrop.io (csv or html)",
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    fn importer(&self) -> Box<dyn formats::import::Importer> {
+        use formats::{
+            instapaper::InstapaperImporter, netscape::NetscapeImporter, pocket::PocketImporter,
+            raindrop::RaindropImporter,
+        };
+
+        match self {
+            ImportSource::Bookmarks => Box::new(NetscapeImporter),
+            ImportSource::Pocket => Box::new(PocketImporter),
+            ImportSource::Instapaper => Box::new(InstapaperImporter),
+            ImportSource::Raindrop => Box::new(RaindropImporter),
+        }
+    }
+}
+
+/// The articles of an uploaded file before they are imported.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ImportPreview {
+    pub articles: Vec<Article>,
+    /// urls that can't be imported, like `place:` or `javascript:` links
+    pub invalid: Vec<String>,
+}
+
+/// Upload of an export file of a browser or another service. The file is
+/// read in the browser, previewed and only imported after confirming.
+#[component]
+pub fn ImportFile() -> impl IntoView {
+    let source = RwSignal::new(ImportSource::default());
+    let contents = RwSignal::new(None::<String>);
+    let preview = Action::new(move |contents: &String| {
+        preview_import(source.get_untracked(), contents.clone())
+    });
+    let import =
+        Action::new(move |contents: &String| run_import(source.get_untracked(), contents.clone()));
+
+    let on_file = move |ev: leptos::ev::Event| {
+        let input: HtmlInputElement = event_target(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        leptos::task::spawn_local(async move {
+            let text = wasm_bindgen_futures::JsFuture::from(file.text()).await;
+            if let Some(text) = text.ok().and_then(|t| t.as_string()) {
+                import.value().set(None);
+                preview.dispatch(text.clone());
+                contents.set(Some(text));
+            }
+        });
+    };
+
+    let on_import = move |_| {
+        if let Some(contents) = contents.get() {
+            import.dispatch(contents);
+        }
+    };
+
+    view! {
+        <div class="flex flex-col gap-2 p-2 border-t-2">
+            <label class="underline" for="import-file">
+                "Import from"
+            </label>
+            <select class="border-1 border-gray-200"
+                on:change=move |ev| {
+                    let index = event_target_value(&ev).parse::<usize>().unwrap_or_default();
+                    source.set(ImportSource::ALL[index]);
+                    import.value().set(None);
+                    if let Some(contents) = contents.get_untracked() {
+                        preview.dispatch(contents);
+                    }
+                }>
+                {ImportSource::ALL
+                    .iter()
+                    .enumerate()
+                    .map(|(index, s)| view! {
+                        <option value=index.to_string() selected=move || source.get() == *s>
+                            {s.label()}
+                        </option>
+                    })
+                    .collect_view()}
+            </select>
+            <input type="file" id="import-file" accept=".html,.htm,.csv,text/html,text/csv"
+                on:change=on_file />
+            {move || match (preview.pending().get(), import.pending().get()) {
+                (true, _) | (_, true) => CenteredLoader().into_any(),
+                _ => ().into_any(),
+            }}
+            {move || match import.value().get() {
+                Some(Ok(report)) => Report(ReportProps { report }).into_any(),
+                Some(Err(err)) => view! {
+                    <p class="text-red-700">{err.to_string()}</p>
+                }.into_any(),
+                None => match preview.value().get() {
+                    Some(Ok(preview)) => view! {
+                        <Preview preview />
+                        <Show when=move || !import.pending().get()>
+                            <div class="flex flex-row-reverse">
+                                <button on:click=on_import>
+                                    <Button>"Import"</Button>
+                                </button>
+                            </div>
+                        </Show>
+                    }.into_any(),
+                    Some(Err(err)) => view! {
+                        <p class="text-red-700">{err.to_string()}</p>
+                    }.into_any(),
+                    None => ().into_any(),
+                },
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn Preview(preview: ImportPreview) -> impl IntoView {
+    let ImportPreview { articles, invalid } = preview;
+
+    view! {
+        <h3 class="underline">
+            {format!("{} articles found, {} can't be imported", articles.len(), invalid.len())}
+        </h3>
+        <ul class="flex flex-col gap-2 max-h-150 overflow-y-auto">
+            {articles.into_iter().map(|article| view! {
+                <li class="flex flex-col">
+                    <span class="wrap-break-word">{article.title}</span>
+                    <span class="text-blue-600 wrap-break-word">{article.url}</span>
+                    <div class="flex gap-2">
+                        <ArticleDate date=Signal::stored(article.created_at) label="added " />
+                        {article.read_at.map(|read_at| view! {
+                            <ArticleDate date=Signal::stored(read_at) label="read " />
+                        })}
+                        <TagList tags=Signal::stored(article.tags) />
+                    </div>
+                </li>
+            }).collect_view()}
+            {invalid.into_iter().map(|url| view! {
+                <li class="text-gray-500 wrap-break-word">{url}</li>
+            }).collect_view()}
+        </ul>
+    }
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn preview_import(
+    source: ImportSource,
+    contents: String,
+) -> Result<ImportPreview, ServerFnError> {
+    let records = source
+        .importer()
+        .parse(&contents)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    let mut preview = ImportPreview::default();
+    for record in records {
+        match record.into_article() {
+            Ok(article) => preview.articles.push(article),
+            Err(invalid) => preview.invalid.push(invalid.text),
+        }
+    }
+    Ok(preview)
+}
+
+/// Imports the file through the same path as `add_articles`, articles that
+/// are already collected are skipped so an import can safely be repeated.
+#[server(
+    client = AuthClient
+)]
+async fn run_import(
+    source: ImportSource,
+    contents: String,
+) -> Result<SubmissionReport, ServerFnError> {
+    use crate::{jobs::insert_and_enrich, ServerState};
+    use domain::submission::LineOutcome;
+
+    let records = source
+        .importer()
+        .parse(&contents)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    let entries = records
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| {
+            let url = record.url.clone();
+            let article = record
+                .into_article()
+                .map_err(|invalid| LineOutcome::InvalidUrl(invalid.error.to_string()));
+            (index + 1, url, article)
+        })
+        .collect();
+
+    let state = expect_context::<ServerState>();
+    Ok(insert_and_enrich(entries, &state.fetcher, &state.db).await?)
+}
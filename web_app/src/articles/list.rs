@@ -1,10 +1,24 @@
-use domain::articles::Article;
-use leptos::{component, prelude::*, server, view, IntoView};
-use leptos_router::components::A;
+use domain::{
+    articles::{Article, ArticleFilter},
+    pagination::{ArticlePage, Cursor},
+    urls::with_query,
+};
+use leptos::{component, html::Div, prelude::*, server, view, IntoView};
+use leptos_router::{components::A, hooks::use_query_map};
+use leptos_use::use_element_visibility;
+use tracing::error;
 use uuid::Uuid;
 
 use crate::{
-    articles::{delete::list::open_delete_dialog_action_list, ArticleUrl},
+    articles::{
+        delete::list::open_delete_dialog_action_list,
+        enrichment::EnrichmentState,
+        read::{mark_read_on_open, set_read_action, ReadToggle},
+        search::{search_articles, SearchBox},
+        tags::{TagFilter, TagList},
+        ArticleDate, ArticleFavicon, ArticlePreviewImage, ArticleReadingTime, ArticleUrl,
+        LinkHealthBadge,
+    },
     keycloak::ShowWhenAuthenticated,
     utils::{
         dialog::DialogSignal,
@@ -13,19 +27,93 @@ use crate::{
     },
 };
 
+/// How many articles are loaded at once, more are loaded when the user
+/// scrolls to the end of the list
+const PAGE_SIZE: u64 = 50;
+
 #[component]
 pub fn ArticlesList() -> impl IntoView {
-    let articles_fn = OnceResource::new(async { get_articles().await.unwrap() });
+    let query = use_query_map();
+    let tag = Signal::derive(move || query.read().get("tag"));
+    let search = Signal::derive(move || query.read().get("q").filter(|q| !q.trim().is_empty()));
+    // the list is used as a reading list, so it only shows unread articles
+    // unless all of them are requested explicitly
+    let unread_only = Signal::derive(move || query.read().get("read").as_deref() != Some("all"));
+    let max_minutes = Signal::derive(move || query.read().get("minutes")?.parse().ok());
+    let filter = Signal::derive(move || ArticleFilter {
+        tag: tag.get(),
+        unread_only: unread_only.get(),
+        max_minutes: max_minutes.get(),
+    });
+    let first_page_fn = Resource::new(
+        move || (filter.get(), search.get()),
+        |(filter, search)| async move {
+            match search {
+                Some(search) => {
+                    ArticlePage::complete(search_articles(search, filter).await.unwrap())
+                }
+                None => get_articles(filter, None).await.unwrap(),
+            }
+        },
+    );
+    let tag_href = Callback::new(move |tag: Option<String>| {
+        list_href(
+            search.get(),
+            ArticleFilter {
+                tag,
+                ..filter.get()
+            },
+        )
+    });
     let dialog = expect_context::<DialogSignal>();
+
+    let articles = RwSignal::new(Vec::<RwSignal<Article>>::new());
+    let next = RwSignal::new(None::<Cursor>);
+    let open_delete_dialog = open_delete_dialog_action_list(dialog, articles);
+
+    let load_more = Action::new(move |cursor: &Cursor| {
+        let cursor = *cursor;
+        let filter = filter.get_untracked();
+        async move {
+            match get_articles(filter, Some(cursor)).await {
+                Ok(page) => {
+                    articles.update(|articles| {
+                        articles.extend(page.articles.into_iter().map(RwSignal::new))
+                    });
+                    next.set(page.next);
+                }
+                Err(err) => error!("failed to load more articles: {err}"),
+            }
+        }
+    });
+
+    let end_of_list = NodeRef::<Div>::new();
+    let end_is_visible = use_element_visibility(end_of_list);
+    Effect::new(move |_| {
+        if end_is_visible.get() && !load_more.pending().get() {
+            if let Some(cursor) = next.get() {
+                load_more.dispatch(cursor);
+            }
+        }
+    });
+
     view! {
+        <SearchBox query=search filter />
+        <TagFilter selected=tag href=tag_href />
+        <div class="flex flex-wrap justify-between">
+            <div class="flex flex-wrap">
+                <ReadFilter search filter />
+                <LengthFilter search filter />
+            </div>
+            <Show when=move || search.read().is_none()>
+                <ExportLinks filter />
+            </Show>
+        </div>
         <Suspense fallback=CenteredLoader>
             {Suspend::new(async move {
-                let articles = articles_fn.await
-                    .into_iter()
-                    .map(RwSignal::new)
-                    .collect::<Vec<_>>();
-                let articles = RwSignal::new(articles);
-                let open_delete_dialog = open_delete_dialog_action_list(dialog, articles);
+                let page = first_page_fn.await;
+                articles.set(page.articles.into_iter().map(RwSignal::new).collect());
+                next.set(page.next);
 
                 { view! {
                     <div class="flex flex-col gap-6 md:gap-2">
@@ -39,6 +127,123 @@ pub fn ArticlesList() -> impl IntoView {
                 }}
              })}
         </Suspense>
+        <div node_ref=end_of_list>
+            <Show when=move || load_more.pending().get()>
+                <CenteredLoader />
+            </Show>
+        </div>
+    }
+}
+
+/// Switches between showing only the unread and all articles while keeping
+/// the search and the other filters.
+#[component]
+fn ReadFilter(search: Signal<Option<String>>, filter: Signal<ArticleFilter>) -> impl IntoView {
+    let unread_only = Signal::derive(move || filter.read().unread_only);
+    let href = move |unread_only| {
+        list_href(
+            search.get(),
+            ArticleFilter {
+                unread_only,
+                ..filter.get()
+            },
+        )
+    };
+
+    view! {
+        <div class="flex gap-2 px-2">
+            <A href=move || href(true)>
+                <span class:underline=move || unread_only.get()>"unread"</span>
+            </A>
+            "|"
+            <A href=move || href(false)>
+                <span class:underline=move || !unread_only.get()>"all"</span>
+            </A>
+        </div>
+    }
+}
+
+/// Limits the list to articles that can be read within a few minutes while
+/// keeping the search and the other filters.
+#[component]
+fn LengthFilter(search: Signal<Option<String>>, filter: Signal<ArticleFilter>) -> impl IntoView {
+    let max_minutes = Signal::derive(move || filter.read().max_minutes);
+    let href = move |max_minutes| {
+        list_href(
+            search.get(),
+            ArticleFilter {
+                max_minutes,
+                ..filter.get()
+            },
+        )
+    };
+    let lengths = [None, Some(5), Some(10), Some(30)];
+
+    view! {
+        <div class="flex gap-2 px-2">
+            {lengths
+                .into_iter()
+                .enumerate()
+                .map(|(i, minutes)| view! {
+                    {(i > 0).then_some("|")}
+                    <A href=move || href(minutes)>
+                        <span class:underline=move || max_minutes.get() == minutes>
+                            {match minutes {
+                                Some(minutes) => format!("under {minutes} min"),
+                                None => String::from("any length"),
+                            }}
+                        </span>
+                    </A>
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+/// Link to the list showing the search results or articles matching the
+/// filter.
+fn list_href(search: Option<String>, filter: ArticleFilter) -> String {
+    let read = match filter.unread_only {
+        true => "unread",
+        false => "all",
+    };
+    let minutes = filter.max_minutes.map(|minutes| minutes.to_string());
+    let pairs = [
+        search.as_deref().map(|search| ("q", search)),
+        Some(("read", read)),
+        filter.tag.as_deref().map(|tag| ("tag", tag)),
+        minutes.as_deref().map(|minutes| ("minutes", minutes)),
+    ];
+    with_query("/articles", pairs.into_iter().flatten())
+}
+
+/// Downloads of the articles matching the current filter.
+#[component]
+fn ExportLinks(filter: Signal<ArticleFilter>) -> impl IntoView {
+    let href = move |format: &str| {
+        let ArticleFilter {
+            tag,
+            unread_only,
+            max_minutes,
+        } = filter.get();
+        let unread_only = unread_only.to_string();
+        let minutes = max_minutes.map(|minutes| minutes.to_string());
+        let pairs = [
+            Some(("unread_only", unread_only.as_str())),
+            tag.as_deref().map(|tag| ("tag", tag)),
+            minutes.as_deref().map(|minutes| ("max_minutes", minutes)),
+        ];
+        with_query(&format!("/export/{format}"), pairs.into_iter().flatten())
+    };
+
+    view! {
+        <div class="flex gap-2 px-2 text-gray-500">
+            "export"
+            {["json", "csv", "html"]
+                .into_iter()
+                .map(|format| view! { <a href=move || href(format) download>{format}</a> })
+                .collect_view()}
+        </div>
     }
 }
 
@@ -48,21 +253,43 @@ fn ArticleInList(
     open_delete_dialog: Action<Uuid, ()>,
 ) -> impl IntoView {
     let width = use_width();
+    let set_read = set_read_action(article);
     view! {
         <div class="flex flex-col gap-1 p-2 relative overflow-hidden">
             <A href=move || format!("/articles/{}", article.read().uuid)>
-                <h3 class="text-2xl text-wrap">{ move || article.get().title }</h3>
+                <h3 class="text-2xl text-wrap"
+                    class:text-gray-500=move || article.read().is_read()>
+                    <ArticleFavicon hash=Signal::derive(move || article.get().favicon) />
+                    { move || article.get().title }
+                </h3>
             </A>
             <ArticleUrl url=Signal::derive(move || article.get().url) add_classes="md:block hidden" />
+            {move || article.get().description.map(|description| view! {
+                <p class="line-clamp-2 text-gray-700">{description}</p>
+            })}
+            <ArticlePreviewImage hash=Signal::derive(move || article.get().preview_image)
+                add_classes="max-h-32" />
+            <TagList tags=Signal::derive(move || article.get().tags) />
+            <div class="flex gap-4">
+                <ArticleDate date=Signal::derive(move || article.get().created_at) />
+                <ArticleReadingTime reading_time=Signal::derive(move || article.get().reading_time) />
+                <LinkHealthBadge health=Signal::derive(move || article.get().link_health) />
+                {move || article.get().found_via.map(|found_via| view! {
+                    <a href=found_via target="_blank" class="text-blue-600">"found via"</a>
+                })}
+                <EnrichmentState article />
+            </div>
             <div class="mx-2 my-1 absolute top-0 right-0 flex gap-2">
                 <Show when=width.is_md()>
-                    <a href=move || article.get().url target="_blank" class="text-blue-600">
+                    <a href=move || article.get().url target="_blank" class="text-blue-600"
+                        on:click=move |_| mark_read_on_open(article, set_read)>
                         <Button>
                             "->"
                         </Button>
                     </a>
                 </Show>
                 <ShowWhenAuthenticated>
+                    <ReadToggle article set_read />
                     <button on:click=move |_| { open_delete_dialog.dispatch(article.read().uuid); }>
                         <Button>
                             "x"
@@ -75,10 +302,13 @@ fn ArticleInList(
 }
 
 #[server(prefix = "/public/api")]
-async fn get_articles() -> Result<Vec<Article>, ServerFnError> {
+async fn get_articles(
+    filter: ArticleFilter,
+    after: Option<Cursor>,
+) -> Result<ArticlePage, ServerFnError> {
     use crate::ServerState;
     use database::articles_query;
 
     let state = expect_context::<ServerState>();
-    Ok(articles_query::all(&state.db).await?)
+    Ok(articles_query::page(&state.db, &filter, after, PAGE_SIZE).await?)
 }
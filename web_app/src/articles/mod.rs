@@ -1,9 +1,20 @@
+use chrono::{DateTime, Utc};
+use domain::{images::image_url, link_health::LinkHealth, reading_time::ReadingTime};
 use leptos::prelude::*;
 
+pub mod archive;
 pub mod delete;
 pub mod edit;
+pub mod enrichment;
+pub mod import;
 pub mod list;
+pub mod read;
+pub mod reader;
+pub mod search;
 pub mod single;
+pub mod tags;
+pub mod trash;
+pub mod update;
 
 #[component]
 pub fn ArticleUrl(
@@ -18,3 +29,82 @@ pub fn ArticleUrl(
         </a>
     }
 }
+
+/// Shows that the link of the article is broken or moved, nothing while it
+/// is healthy or wasn't checked yet.
+#[component]
+pub fn LinkHealthBadge(health: Signal<Option<LinkHealth>>) -> impl IntoView {
+    move || {
+        health.get().map(|health| {
+            let color = match health {
+                LinkHealth::Broken { .. } => "bg-red-600",
+                LinkHealth::Moved { .. } => "bg-amber-500",
+            };
+            view! {
+                <span class=format!("px-1 rounded text-white {color}") title=health.reason()>
+                    {health.label()}
+                </span>
+            }
+        })
+    }
+}
+
+/// The stored favicon of the article's site, nothing while there is none.
+#[component]
+pub fn ArticleFavicon(hash: Signal<Option<String>>) -> impl IntoView {
+    move || {
+        hash.get().map(|hash| {
+            view! {
+                <img src=image_url(&hash) alt="" loading="lazy"
+                    class="inline size-5 mr-2 align-baseline" />
+            }
+        })
+    }
+}
+
+/// The stored preview image of the article, nothing while there is none.
+#[component]
+pub fn ArticlePreviewImage(
+    hash: Signal<Option<String>>,
+    #[prop(default = "")] add_classes: &'static str,
+) -> impl IntoView {
+    move || {
+        hash.get().map(|hash| {
+            view! {
+                <img src=image_url(&hash) alt="" loading="lazy"
+                    class=["w-fit rounded object-contain", add_classes].join(" ") />
+            }
+        })
+    }
+}
+
+/// How long reading the article takes, nothing while its content wasn't
+/// extracted yet.
+#[component]
+pub fn ArticleReadingTime(reading_time: Signal<Option<ReadingTime>>) -> impl IntoView {
+    move || {
+        reading_time.get().map(|time| {
+            view! {
+                <span class="text-gray-500" title=format!("{} words", time.words)>
+                    {format!("{} min read", time.minutes)}
+                </span>
+            }
+        })
+    }
+}
+
+#[component]
+pub fn ArticleDate(
+    date: Signal<DateTime<Utc>>,
+    #[prop(default = "")] label: &'static str,
+) -> impl IntoView {
+    view! {
+        <span class="text-gray-500">
+            { move || format!("{label}{}", format_date(&date.get())) }
+        </span>
+    }
+}
+
+pub fn format_date(date: &DateTime<Utc>) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
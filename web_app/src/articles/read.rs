@@ -0,0 +1,61 @@
+use domain::articles::Article;
+use leptos::{prelude::*, server};
+use leptos_oidc::AuthSignal;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{keycloak::AuthClient, utils::Button};
+
+/// Action that marks the article as read or unread and puts the changed
+/// article back into the signal.
+pub fn set_read_action(article: RwSignal<Article>) -> Action<bool, ()> {
+    Action::new(move |read: &bool| {
+        let read = *read;
+        let uuid = article.read_untracked().uuid;
+        async move {
+            match set_article_read(uuid, read).await {
+                Ok(updated) => article.set(updated),
+                Err(err) => error!("failed to change read state of {uuid}: {err}"),
+            }
+        }
+    })
+}
+
+/// Marks an unread article as read when its link is opened, does nothing for
+/// users that are not logged in.
+pub fn mark_read_on_open(article: RwSignal<Article>, set_read: Action<bool, ()>) {
+    let is_authenticated = use_context::<AuthSignal>()
+        .map(|auth| auth.get_untracked().is_authenticated())
+        .unwrap_or(false);
+
+    if is_authenticated && !article.read_untracked().is_read() {
+        set_read.dispatch(true);
+    }
+}
+
+#[component]
+pub fn ReadToggle(article: RwSignal<Article>, set_read: Action<bool, ()>) -> impl IntoView {
+    view! {
+        <button
+            disabled=move || set_read.pending().get()
+            on:click=move |_| { set_read.dispatch(!article.read().is_read()); }>
+            <Button>
+                { move || match article.read().is_read() {
+                    true => "mark unread",
+                    false => "mark read",
+                }}
+            </Button>
+        </button>
+    }
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn set_article_read(article_uuid: Uuid, read: bool) -> Result<Article, ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(articles_query::set_read(article_uuid, read, &state.db).await?)
+}
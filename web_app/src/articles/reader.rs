@@ -0,0 +1,50 @@
+use domain::reader::ArticleContent;
+use leptos::{prelude::*, server};
+use uuid::Uuid;
+
+/// Styles for the allowlisted tags of the extracted content
+const READER_CLASSES: &str = "flex flex-col gap-4 text-xl leading-relaxed wrap-break-word \
+    [&_h1]:text-3xl [&_h2]:text-2xl [&_h3]:text-xl [&_h3]:font-bold [&_a]:text-blue-600 \
+    [&_img]:max-w-full [&_figcaption]:text-gray-500 [&_pre]:overflow-x-auto \
+    [&_pre]:bg-gray-100 [&_pre]:p-2 [&_ul]:list-disc [&_ul]:pl-6 [&_ol]:list-decimal \
+    [&_ol]:pl-6 [&_blockquote]:border-l-4 [&_blockquote]:border-gray-300 [&_blockquote]:pl-4";
+
+/// The readable content of the article, extracted from its page in the
+/// background.
+#[component]
+pub fn ReaderView(uuid: Uuid) -> impl IntoView {
+    let content = OnceResource::new(async move {
+        get_article_content(uuid)
+            .await
+            .map_err(|err| err.to_string())
+    });
+
+    view! {
+        <Suspense fallback=|| view! { <p class="text-xl">"Loading....."</p> }>
+            {Suspend::new(async move { match content.await {
+                Ok(Some(content)) => view! {
+                    <article class=READER_CLASSES inner_html=content.html></article>
+                }.into_any(),
+                Ok(None) => view! {
+                    <p class="text-xl text-gray-500">
+                        "There is no readable content of this article (yet)."
+                    </p>
+                }.into_any(),
+                Err(err) => view! {
+                    <p class="text-xl text-red-600">
+                        {format!("Loading the content failed: {err}")}
+                    </p>
+                }.into_any(),
+            }})}
+        </Suspense>
+    }
+}
+
+#[server(prefix = "/public/api")]
+async fn get_article_content(uuid: Uuid) -> Result<Option<ArticleContent>, ServerFnError> {
+    use crate::ServerState;
+    use database::contents_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(contents_query::one(&state.db, uuid).await?)
+}
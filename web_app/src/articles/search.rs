@@ -0,0 +1,49 @@
+use domain::articles::{Article, ArticleFilter};
+use leptos::prelude::*;
+use leptos_router::components::Form;
+
+use crate::utils::Button;
+
+/// Search input that submits the query as the `q` url parameter, so that
+/// search results can be linked. The current filter is kept.
+#[component]
+pub fn SearchBox(query: Signal<Option<String>>, filter: Signal<ArticleFilter>) -> impl IntoView {
+    view! {
+        <Form method="GET" action="/articles">
+            <input type="hidden" name="read"
+                prop:value=move || match filter.read().unread_only {
+                    true => "unread",
+                    false => "all",
+                } />
+            {move || filter.get().tag.map(|tag| view! {
+                <input type="hidden" name="tag" prop:value=tag />
+            })}
+            {move || filter.get().max_minutes.map(|minutes| view! {
+                <input type="hidden" name="minutes" prop:value=minutes.to_string() />
+            })}
+            <div class="flex gap-2 p-2">
+                <input type="search" name="q"
+                    placeholder="search titles, urls and descriptions"
+                    prop:value=move || query.get().unwrap_or_default()
+                    class="w-full border-1 border-gray-200 px-1 text-xl" />
+                <Button>
+                    <input type="submit" value="Search" />
+                </Button>
+            </div>
+        </Form>
+    }
+}
+
+/// The best matches of the articles matching the filter, at most
+/// [`SEARCH_LIMIT`](database::articles_query::SEARCH_LIMIT) of them.
+#[server(prefix = "/public/api")]
+pub async fn search_articles(
+    query: String,
+    filter: ArticleFilter,
+) -> Result<Vec<Article>, ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(articles_query::search(&state.db, &query, &filter).await?)
+}
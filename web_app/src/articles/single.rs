@@ -4,7 +4,16 @@ use leptos_router::{components::A, hooks::use_params, params::Params};
 use uuid::Uuid;
 
 use crate::{
-    articles::{delete::single::open_delete_dialog_action, ArticleUrl},
+    articles::{
+        archive::{ArchiveButton, ArchiveLink},
+        delete::single::open_delete_dialog_action,
+        enrichment::EnrichmentState,
+        read::{mark_read_on_open, set_read_action, ReadToggle},
+        reader::ReaderView,
+        tags::TagList,
+        update::EditArticleForm,
+        ArticleDate, ArticleFavicon, ArticlePreviewImage, ArticleReadingTime, ArticleUrl,
+    },
     keycloak::ShowWhenAuthenticated,
     utils::{busy_container::BusyContainer, Button, CenterColumn},
 };
@@ -52,6 +61,9 @@ pub fn LoadingArticle(uuid: Uuid) -> impl IntoView {
 #[component]
 pub fn ArticleView(article: RwSignal<Article>) -> impl IntoView {
     let delete_dialog = open_delete_dialog_action(None);
+    let editing = RwSignal::new(false);
+    let reading = RwSignal::new(false);
+    let set_read = set_read_action(article);
 
     view! {
         <BusyContainer busy_state=delete_dialog>
@@ -62,12 +74,68 @@ pub fn ArticleView(article: RwSignal<Article>) -> impl IntoView {
                     </Button>
                 </A>
             </div>
-            <div class="mb-4">
-                <Title> {move || article.get().title} </Title>
-                <ArticleUrl url=Signal::derive(move || article.get().url)
-                    add_classes="text-3xl wrap-break-word" />
-            </div>
-            <div class="flex flex-row-reverse gap-2">
+            <Show when=move || !editing.get()
+                fallback=move || view! { <EditArticleForm article editing /> }>
+                <div class="mb-4">
+                    <Title>
+                        <ArticleFavicon hash=Signal::derive(move || article.get().favicon) />
+                        {move || article.get().title}
+                    </Title>
+                    <ArticleUrl url=Signal::derive(move || article.get().url)
+                        add_classes="text-3xl wrap-break-word" />
+                </div>
+                <div class="flex gap-4 mb-4 text-xl border-b-1 border-gray-200">
+                    <button class:font-bold=move || !reading.get()
+                        on:click=move |_| reading.set(false)>
+                        "details"
+                    </button>
+                    <button class:font-bold=move || reading.get()
+                        on:click=move |_| reading.set(true)>
+                        "reader"
+                    </button>
+                </div>
+                <div class="mb-4" class:hidden=move || !reading.get()>
+                    {move || reading.get().then(|| view! {
+                        <ReaderView uuid=article.read_untracked().uuid />
+                    })}
+                </div>
+                <div class="flex flex-col gap-1 mb-4 text-xl" class:hidden=move || reading.get()>
+                    <div class="flex gap-4">
+                        <ArticleDate date=Signal::derive(move || article.get().created_at)
+                            label="added " />
+                        <ArticleDate date=Signal::derive(move || article.get().updated_at)
+                            label="updated " />
+                        {move || article.get().read_at.map(|read_at| view! {
+                            <ArticleDate date=Signal::stored(read_at) label="read " />
+                        })}
+                        <ArticleReadingTime
+                            reading_time=Signal::derive(move || article.get().reading_time) />
+                        <ArchiveLink uuid=article.read_untracked().uuid />
+                        <EnrichmentState article />
+                    </div>
+                    <TagList tags=Signal::derive(move || article.get().tags) />
+                    {move || article.get().description.map(|description| view! {
+                        <p class="wrap-break-word">{description}</p>
+                    })}
+                    <ArticlePreviewImage hash=Signal::derive(move || article.get().preview_image)
+                        add_classes="max-h-80" />
+                    {move || article.get().found_via.map(|found_via| view! {
+                        <p>
+                            "found via "
+                            <ArticleUrl url=Signal::stored(found_via)
+                                add_classes="wrap-break-word" />
+                        </p>
+                    })}
+                    {move || article.get().original_url.map(|original_url| view! {
+                        <p>
+                            "submitted as "
+                            <ArticleUrl url=Signal::stored(original_url)
+                                add_classes="wrap-break-word" />
+                        </p>
+                    })}
+                </div>
+            </Show>
+            <div class="flex flex-row-reverse gap-2" class:hidden=move || editing.get()>
                 <ShowWhenAuthenticated>
                     <button on:click=move |_| {
                         delete_dialog.open_dialog(article.read().uuid);
@@ -76,8 +144,16 @@ pub fn ArticleView(article: RwSignal<Article>) -> impl IntoView {
                             "delete"
                         </Button>
                     </button>
+                    <button on:click=move |_| editing.set(true)>
+                        <Button>
+                            "edit"
+                        </Button>
+                    </button>
+                    <ArchiveButton article />
+                    <ReadToggle article set_read />
                 </ShowWhenAuthenticated>
-                <a href=move || article.get().url target="_blank">
+                <a href=move || article.get().url target="_blank"
+                    on:click=move |_| mark_read_on_open(article, set_read)>
                     <Button>
                         <div>"open"</div>
                     </Button>
@@ -0,0 +1,84 @@
+use domain::{tags::Tag, urls::with_query};
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+/// Links to the article list filtered by each of the tags
+#[component]
+pub fn TagList(tags: Signal<Vec<Tag>>) -> impl IntoView {
+    view! {
+        <div class="flex flex-wrap gap-2">
+            <For each=move || tags.get()
+                key=|tag| tag.uuid
+                let(tag)
+            >
+                <TagLink name=tag.name />
+            </For>
+        </div>
+    }
+}
+
+#[component]
+pub fn TagLink(name: String) -> impl IntoView {
+    view! {
+        <A href=with_query("/articles", [("tag", name.as_str())])>
+            <span class="text-sm border-1 border-gray-300 px-1 hover:bg-black hover:text-white">
+                {format!("#{name}")}
+            </span>
+        </A>
+    }
+}
+
+/// Row of all existing tags, the currently selected one is highlighted and
+/// clicking it again removes the filter. `href` links to the list filtered
+/// by the passed tag, or to the unfiltered list for `None`.
+#[component]
+pub fn TagFilter(
+    selected: Signal<Option<String>>,
+    href: Callback<Option<String>, String>,
+) -> impl IntoView {
+    let tags_fn = OnceResource::new(async { get_tags().await.unwrap_or_default() });
+
+    view! {
+        <Suspense fallback=|| ()>
+            {Suspend::new(async move {
+                let tags = tags_fn.await;
+                view! {
+                    <div class="flex flex-wrap gap-2 p-2">
+                        {tags.into_iter().map(|tag| {
+                            let name = tag.name.clone();
+                            let is_selected = {
+                                let name = name.clone();
+                                move || selected.read().as_ref().is_some_and(|s| s.eq(&name))
+                            };
+                            let link = {
+                                let is_selected = is_selected.clone();
+                                move || match is_selected() {
+                                    true => href.run(None),
+                                    false => href.run(Some(name.clone())),
+                                }
+                            };
+                            view! {
+                                <A href=link>
+                                    <span class="border-1 border-gray-300 px-1 hover:bg-black hover:text-white"
+                                        class=("bg-black", is_selected.clone())
+                                        class=("text-white", is_selected)>
+                                        {format!("#{}", tag.name)}
+                                    </span>
+                                </A>
+                            }
+                        }).collect_view()}
+                    </div>
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[server(prefix = "/public/api")]
+pub async fn get_tags() -> Result<Vec<Tag>, ServerFnError> {
+    use crate::ServerState;
+    use database::tags_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(tags_query::all(&state.db).await?)
+}
@@ -0,0 +1,169 @@
+use domain::articles::Article;
+use leptos::{prelude::*, server};
+use uuid::Uuid;
+
+use crate::{
+    articles::{ArticleDate, ArticleUrl},
+    keycloak::{AuthClient, ExpectAuth},
+    utils::{
+        dialog::{DialogSignal, DialogState},
+        Button, CenterColumn, CenteredLoader,
+    },
+};
+
+#[component]
+pub fn TrashPage() -> impl IntoView {
+    view! {
+        <CenterColumn>
+            <ExpectAuth>
+                <TrashList />
+            </ExpectAuth>
+        </CenterColumn>
+    }
+}
+
+#[component]
+fn TrashList() -> impl IntoView {
+    let trashed_fn = LocalResource::new(get_trashed_articles);
+    let dialog = expect_context::<DialogSignal>();
+
+    view! {
+        <h2 class="text-3xl p-2">"Trash"</h2>
+        <Suspense fallback=CenteredLoader>
+            {move || Suspend::new(async move {
+                match trashed_fn.await {
+                    Ok(articles) => {
+                        let articles = RwSignal::new(
+                            articles.into_iter().map(RwSignal::new).collect::<Vec<_>>()
+                        );
+                        let restore = restore_action(articles);
+                        let purge = open_purge_dialog_action(dialog, articles);
+
+                        view! {
+                            <Show when=move || articles.read().is_empty()>
+                                <p class="p-2 text-gray-500">"The trash is empty"</p>
+                            </Show>
+                            <div class="flex flex-col gap-6 md:gap-2">
+                                <For each=move || articles.get()
+                                    key=|state| state.read().uuid
+                                    let(article)
+                                >
+                                    <ArticleInTrash article restore purge />
+                                </For>
+                            </div>
+                        }.into_any()
+                    }
+                    Err(err) => view! {
+                        <p class="p-2 text-red-600">{err.to_string()}</p>
+                    }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn ArticleInTrash(
+    article: RwSignal<Article>,
+    restore: Action<Uuid, ()>,
+    purge: Action<Uuid, ()>,
+) -> impl IntoView {
+    view! {
+        <div class="flex flex-col gap-1 p-2 relative overflow-hidden">
+            <h3 class="text-2xl text-wrap">{ move || article.get().title }</h3>
+            <ArticleUrl url=Signal::derive(move || article.get().url) add_classes="md:block hidden" />
+            {move || article.get().deleted_at.map(|deleted_at| view! {
+                <ArticleDate date=Signal::stored(deleted_at) label="trashed " />
+            })}
+            <div class="mx-2 my-1 absolute top-0 right-0 flex gap-2">
+                <button on:click=move |_| { restore.dispatch(article.read().uuid); }>
+                    <Button>
+                        "restore"
+                    </Button>
+                </button>
+                <button on:click=move |_| { purge.dispatch(article.read().uuid); }>
+                    <Button>
+                        "purge"
+                    </Button>
+                </button>
+            </div>
+        </div>
+    }
+}
+
+fn restore_action(articles: RwSignal<Vec<RwSignal<Article>>>) -> Action<Uuid, ()> {
+    Action::new(move |uuid: &Uuid| {
+        let uuid = *uuid;
+        async move {
+            if restore_article(uuid).await.is_ok() {
+                remove_from_list(articles, uuid);
+            }
+        }
+    })
+}
+
+fn open_purge_dialog_action(
+    dialog: DialogSignal,
+    articles: RwSignal<Vec<RwSignal<Article>>>,
+) -> Action<Uuid, ()> {
+    let purge_action = Action::new(move |uuid: &Uuid| {
+        let uuid = *uuid;
+        async move {
+            if purge_article(uuid).await.is_ok() {
+                remove_from_list(articles, uuid);
+            }
+        }
+    });
+
+    Action::new(move |uuid: &Uuid| {
+        let uuid = *uuid;
+        async move {
+            dialog.open(DialogState::yes(
+                move || {
+                    purge_action.dispatch(uuid);
+                },
+                "Purge Item?",
+                "Do you really want to permanently delete this Item? This can't be undone.",
+            ));
+        }
+    })
+}
+
+fn remove_from_list(articles: RwSignal<Vec<RwSignal<Article>>>, uuid: Uuid) {
+    articles.update(move |articles| {
+        let _ = articles.extract_if(.., |a| a.read().uuid.eq(&uuid)).count();
+    });
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn get_trashed_articles() -> Result<Vec<Article>, ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(articles_query::trashed(&state.db).await?)
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn restore_article(article_uuid: Uuid) -> Result<(), ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(articles_query::restore(article_uuid, &state.db).await?)
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn purge_article(article_uuid: Uuid) -> Result<(), ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(articles_query::purge(article_uuid, &state.db).await?)
+}
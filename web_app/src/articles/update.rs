@@ -0,0 +1,127 @@
+use domain::articles::Article;
+use leptos::{prelude::*, server};
+use uuid::Uuid;
+
+use crate::{keycloak::AuthClient, utils::Button};
+
+/// Inline form to change the title, url and metadata of an article, the
+/// article signal is only changed once the server accepted the update.
+#[component]
+pub fn EditArticleForm(article: RwSignal<Article>, editing: RwSignal<bool>) -> impl IntoView {
+    let current = article.get_untracked();
+    let title = RwSignal::new(current.title);
+    let url = RwSignal::new(current.url);
+    let description = RwSignal::new(current.description.unwrap_or_default());
+    let found_via = RwSignal::new(current.found_via.unwrap_or_default());
+    let tags = RwSignal::new(
+        current
+            .tags
+            .into_iter()
+            .map(|t| t.name)
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    let last_err = RwSignal::new(None::<String>);
+
+    let save_action = Action::new(move |_: &()| {
+        let uuid = article.read_untracked().uuid;
+        let (title, url, description, found_via, tags) = (
+            title.get_untracked(),
+            url.get_untracked(),
+            description.get_untracked(),
+            found_via.get_untracked(),
+            tags.get_untracked(),
+        );
+        async move {
+            match update_article(uuid, title, url, description, found_via, tags).await {
+                Ok(updated) => {
+                    article.set(updated);
+                    editing.set(false);
+                    last_err.set(None);
+                }
+                Err(err) => last_err.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    view! {
+        <form class="flex flex-col gap-2 mb-4 text-xl"
+            on:submit=move |ev| {
+                ev.prevent_default();
+                save_action.dispatch(());
+            }>
+            <label class="underline" for="title">"Title"</label>
+            <input type="text" id="title" bind:value=title
+                class="border-1 border-gray-200 px-1" />
+            <label class="underline" for="url">"Url"</label>
+            <input type="url" id="url" bind:value=url
+                class="border-1 border-gray-200 px-1" />
+            <label class="underline" for="description">"Description"</label>
+            <textarea id="description" bind:value=description
+                class="field-sizing-content min-h-20 border-1 border-gray-200 px-1">
+            </textarea>
+            <label class="underline" for="found_via">"Found via"</label>
+            <input type="url" id="found_via" bind:value=found_via
+                class="border-1 border-gray-200 px-1" />
+            <label class="underline" for="tags">"Tags (comma separated)"</label>
+            <input type="text" id="tags" bind:value=tags
+                class="border-1 border-gray-200 px-1" />
+            {move || last_err.get().map(|err| view! {
+                <p class="text-red-600">{err}</p>
+            })}
+            <div class="flex flex-row-reverse gap-2">
+                <button type="submit" disabled=move || save_action.pending().get()>
+                    <Button>"save"</Button>
+                </button>
+                <button type="button" on:click=move |_| editing.set(false)>
+                    <Button>"cancel"</Button>
+                </button>
+            </div>
+        </form>
+    }
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn update_article(
+    uuid: Uuid,
+    title: String,
+    url: String,
+    description: String,
+    found_via: String,
+    tags: String,
+) -> Result<Article, ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+    use domain::{tags::parse_tag_list, urls::normalize_url};
+    use sea_orm::SqlErr;
+
+    let state = expect_context::<ServerState>();
+
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(ServerFnError::new("the title can't be empty"));
+    }
+    let url = url.trim();
+    normalize_url(url).map_err(|err| ServerFnError::new(format!("invalid url: {err}")))?;
+
+    let Some(mut article) = articles_query::one(&state.db, uuid).await? else {
+        return Err(ServerFnError::new(format!("article {uuid} not found")));
+    };
+
+    article.title = title.into();
+    article.url = url.into();
+    article.description = Some(description.trim().to_owned()).filter(|d| !d.is_empty());
+    article.found_via = Some(found_via.trim().to_owned()).filter(|f| !f.is_empty());
+    article.tags = parse_tag_list(&tags);
+
+    articles_query::update(article, &state.db)
+        .await
+        .map_err(|err| match err.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => {
+                ServerFnError::new("another article already has this url")
+            }
+            _ => ServerFnError::new(err),
+        })
+}
@@ -0,0 +1,11 @@
+use axum::http::{header, HeaderMap};
+
+/// Whether the `If-None-Match` header of the request names the etag, then
+/// the browser still has the response and can be answered with
+/// `304 Not Modified`.
+pub fn has_etag(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|tag| tag.trim() == etag))
+}
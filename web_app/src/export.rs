@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use database::articles_query;
+use domain::articles::ArticleFilter;
+use formats::export::{export, ExportFormat};
+use tracing::error;
+use web_app::ServerState;
+
+/// Downloads all articles matching the filter of the query, for example
+/// `/export/csv?tag=rust&unread_only=true&max_minutes=10`, as `json`, `csv`
+/// or `html`.
+pub async fn export_articles(
+    State(state): State<ServerState>,
+    Path(format): Path<String>,
+    Query(filter): Query<ArticleFilter>,
+) -> Response {
+    let Some(format) = ExportFormat::from_extension(&format) else {
+        return (
+            StatusCode::NOT_FOUND,
+            format!("unknown export format: {format}"),
+        )
+            .into_response();
+    };
+
+    let export = match articles_query::all_matching(&state.db, &filter).await {
+        Ok(articles) => export(&articles, format).map_err(|err| err.to_string()),
+        Err(err) => Err(err.to_string()),
+    };
+
+    match export {
+        Ok(export) => {
+            let file_name = format!(
+                "articles-{}.{}",
+                Utc::now().format("%Y-%m-%d"),
+                format.extension()
+            );
+            (
+                [
+                    (header::CONTENT_TYPE, format.content_type().to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"{file_name}\""),
+                    ),
+                ],
+                export,
+            )
+                .into_response()
+        }
+        Err(err) => {
+            error!("export failed: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                String::from("export failed"),
+            )
+                .into_response()
+        }
+    }
+}
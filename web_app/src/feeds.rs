@@ -0,0 +1,178 @@
+//! Public feeds of the collection at `/feed.rss`, `/feed.atom` and
+//! `/feed.json`. There are no separate collections, the collection is every
+//! article that isn't in the trash. The feeds are narrowed with the filters
+//! of the article list, [`ArticleFilter`]: `tag` for the articles of a tag,
+//! `unread_only=true` for unread ones and `max_minutes` for short ones.
+
+use std::env;
+
+use axum::{
+    extract::{OriginalUri, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use database::articles_query;
+use domain::{
+    articles::{Article, ArticleFilter},
+    pagination::{ArticlePage, Cursor},
+    urls::with_query,
+};
+use formats::feeds::{atom, json_feed, rss, FeedInfo};
+use sea_orm::DbErr;
+use serde::Deserialize;
+use tracing::error;
+use url::form_urlencoded;
+use web_app::ServerState;
+
+/// How many of the newest articles are part of a feed
+const FEED_SIZE: u64 = 50;
+
+/// RSS 2.0 feed of the newest articles matching the filter of the query,
+/// for example `/feed.rss?tag=rust`.
+pub async fn rss_feed(
+    State(state): State<ServerState>,
+    OriginalUri(uri): OriginalUri,
+    Query(filter): Query<ArticleFilter>,
+) -> Response {
+    match feed_articles(&state, &filter).await {
+        Ok(articles) => {
+            let info = feed_info(&filter, &uri.to_string());
+            feed_response("application/rss+xml; charset=utf-8", rss(&info, &articles))
+        }
+        Err(err) => feed_error(err),
+    }
+}
+
+/// Atom feed of the newest articles matching the filter of the query, for
+/// example `/feed.atom?tag=rust`.
+pub async fn atom_feed(
+    State(state): State<ServerState>,
+    OriginalUri(uri): OriginalUri,
+    Query(filter): Query<ArticleFilter>,
+) -> Response {
+    match feed_articles(&state, &filter).await {
+        Ok(articles) => {
+            let info = feed_info(&filter, &uri.to_string());
+            feed_response(
+                "application/atom+xml; charset=utf-8",
+                atom(&info, &articles),
+            )
+        }
+        Err(err) => feed_error(err),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct FeedPage {
+    /// token of the cursor the page starts after
+    after: Option<String>,
+}
+
+/// JSON Feed 1.1 of the articles matching the filter of the query, for
+/// example `/feed.json?tag=rust`. Older articles are reachable through the
+/// `next_url` of each page.
+pub async fn json_feed_page(
+    State(state): State<ServerState>,
+    OriginalUri(uri): OriginalUri,
+    Query(filter): Query<ArticleFilter>,
+    Query(FeedPage { after }): Query<FeedPage>,
+) -> Response {
+    let after = match after.as_deref().map(Cursor::from_token) {
+        Some(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                String::from("invalid `after` cursor"),
+            )
+                .into_response()
+        }
+        Some(cursor) => cursor,
+        None => None,
+    };
+
+    let ArticlePage { articles, next } =
+        match articles_query::page(&state.db, &filter, after, FEED_SIZE).await {
+            Ok(page) => page,
+            Err(err) => return feed_error(err),
+        };
+
+    let info = feed_info(&filter, &uri.to_string());
+    let next_url = next.map(|cursor| next_page_url(&filter, &cursor));
+    match json_feed(&info, &articles, next_url) {
+        Ok(feed) => feed_response("application/feed+json; charset=utf-8", feed),
+        Err(err) => {
+            error!("serializing the json feed failed: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                String::from("feed failed"),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Link to the json feed page after the cursor that keeps the filter.
+fn next_page_url(filter: &ArticleFilter, cursor: &Cursor) -> String {
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    if let Some(tag) = &filter.tag {
+        query.append_pair("tag", tag);
+    }
+    if filter.unread_only {
+        query.append_pair("unread_only", "true");
+    }
+    if let Some(minutes) = filter.max_minutes {
+        query.append_pair("max_minutes", &minutes.to_string());
+    }
+    query.append_pair("after", &cursor.to_token());
+    format!("{}/feed.json?{}", app_url(), query.finish())
+}
+
+async fn feed_articles(state: &ServerState, filter: &ArticleFilter) -> Result<Vec<Article>, DbErr> {
+    let page = articles_query::page(&state.db, filter, None, FEED_SIZE).await?;
+    Ok(page.articles)
+}
+
+/// Absolute urls of the feed and the page it belongs to, feed readers can't
+/// resolve relative ones.
+fn feed_info(filter: &ArticleFilter, path: &str) -> FeedInfo {
+    let app_url = app_url();
+    let (title, site_url) = match &filter.tag {
+        Some(tag) => (
+            format!("Articles tagged {tag}"),
+            format!(
+                "{app_url}{}",
+                with_query("/articles", [("tag", tag.as_str()), ("read", "all")])
+            ),
+        ),
+        None => (
+            String::from("Articles"),
+            format!("{app_url}/articles?read=all"),
+        ),
+    };
+
+    FeedInfo {
+        title,
+        description: String::from("Newest articles of the collection"),
+        site_url,
+        feed_url: format!("{app_url}{path}"),
+    }
+}
+
+fn app_url() -> String {
+    env::var("APP_URL")
+        .expect("env APP_URL not found")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn feed_response(content_type: &'static str, feed: String) -> Response {
+    ([(header::CONTENT_TYPE, content_type)], feed).into_response()
+}
+
+fn feed_error(err: DbErr) -> Response {
+    error!("loading the feed failed: {err}");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        String::from("feed failed"),
+    )
+        .into_response()
+}
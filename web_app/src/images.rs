@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use database::images_query;
+use domain::images::is_image_hash;
+use tracing::error;
+use web_app::ServerState;
+
+use crate::caching::has_etag;
+
+/// Stored images never change because they are addressed by their hash, so
+/// browsers may keep them for a year without asking again
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Serves a stored favicon or preview image, `/images/{hash}`.
+pub async fn serve_image(
+    State(state): State<ServerState>,
+    Path(hash): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if !is_image_hash(&hash) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    let etag = format!("\"{hash}\"");
+    if has_etag(&headers, &etag) {
+        return (
+            StatusCode::NOT_MODIFIED,
+            [
+                (header::ETAG, etag),
+                (header::CACHE_CONTROL, CACHE_CONTROL.to_string()),
+            ],
+        )
+            .into_response();
+    }
+
+    match images_query::one(&state.db, &hash).await {
+        Ok(Some(image)) => (
+            [
+                (header::CONTENT_TYPE, image.content_type),
+                (header::ETAG, etag),
+                (header::CACHE_CONTROL, CACHE_CONTROL.to_string()),
+            ],
+            image.data,
+        )
+            .into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            error!("loading image {hash} failed: {err}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! The background worker that enriches articles after they were added, so
+//! adding articles never waits for other sites.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use database::{
+    articles_query, contents_query, images_query, jobs_query, snapshots_query, tag_rules_query,
+};
+use domain::{
+    images::StoredImage,
+    jobs::{Job, JobKind},
+    reading_time::ReadingTime,
+    submission::{SubmissionEntry, SubmissionReport},
+    tag_rules::RuleSet,
+    urls::needs_resolving,
+};
+use fetcher::{resolver::resolve_article, Fetch};
+use futures::{future::join_all, stream, StreamExt};
+use sea_orm::{DatabaseConnection, DbErr, TransactionTrait};
+use uuid::Uuid;
+
+use crate::ServerState;
+
+/// How many jobs are taken from the queue at once, they run concurrently
+const BATCH_SIZE: u64 = 10;
+
+/// How many short links are resolved at once while inserting
+const CONCURRENT_RESOLVES: usize = 8;
+
+/// How long resolving the short links of a submission may take at most,
+/// links that aren't resolved by then are resolved by a job instead
+const RESOLVE_TIME: Duration = Duration::from_secs(10);
+
+/// A job that isn't done within this time, e.g. because the server stopped
+/// while running it, is taken again
+const LEASE_MINUTES: i64 = 5;
+
+/// Inserts the entries like [`articles_query::insert_reported`] and queues
+/// the enrichment of every inserted article. Short links and AMP pages are
+/// resolved before, so they are detected as duplicates of the article they
+/// lead to. Links that take longer than [`RESOLVE_TIME`] are inserted as
+/// they are and resolved by a [`JobKind::Resolve`] job first, which merges
+/// them into the article they lead to if that is collected already. The tag
+/// rules are applied to the resolved articles.
+pub async fn insert_and_enrich<F>(
+    entries: Vec<SubmissionEntry>,
+    fetcher: &F,
+    db: &DatabaseConnection,
+) -> Result<SubmissionReport, DbErr>
+where
+    F: Fetch,
+{
+    let rules = RuleSet::new(tag_rules_query::all(db).await?);
+    let deadline = tokio::time::Instant::now() + RESOLVE_TIME;
+    let resolved = stream::iter(entries)
+        .map(|(number, line, article)| async move {
+            let (article, timed_out) = match article {
+                Ok(article) if needs_resolving(&article.url) => {
+                    let resolving = resolve_article(fetcher, article.clone());
+                    match tokio::time::timeout_at(deadline, resolving).await {
+                        Ok(resolved) => (Ok(resolved), false),
+                        Err(_) => (Ok(article), true),
+                    }
+                }
+                article => (article, false),
+            };
+            ((number, line, article), timed_out)
+        })
+        .buffered(CONCURRENT_RESOLVES)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut entries = Vec::with_capacity(resolved.len());
+    let mut to_resolve = vec![];
+    let mut to_enrich = vec![];
+    for ((number, line, mut article), timed_out) in resolved {
+        if let Ok(article) = &mut article {
+            rules.apply(article);
+            match timed_out {
+                true => to_resolve.push(article.uuid),
+                false => to_enrich.push(article.uuid),
+            }
+        }
+        entries.push((number, line, article));
+    }
+
+    // the articles are only stored together with their jobs, so none of them
+    // is left without enrichment
+    let txn = db.begin().await?;
+    let report = articles_query::insert_reported(entries, &txn).await?;
+    // duplicates weren't inserted, so they aren't enriched either
+    jobs_query::enqueue(to_resolve, &[JobKind::Resolve], &txn).await?;
+    jobs_query::enqueue(to_enrich, &JobKind::ENRICHMENT, &txn).await?;
+    txn.commit().await?;
+    Ok(report)
+}
+
+/// Runs due jobs forever. The queue is checked every few seconds and
+/// emptied before waiting again.
+pub async fn run_jobs_periodically(state: ServerState) {
+    let mut ticks = tokio::time::interval(Duration::from_secs(5));
+
+    loop {
+        ticks.tick().await;
+        loop {
+            let lease = chrono::Duration::minutes(LEASE_MINUTES);
+            let jobs = match jobs_query::take_due(BATCH_SIZE, lease, &state.db).await {
+                Ok(jobs) => jobs,
+                Err(err) => {
+                    tracing::warn!("taking jobs from the queue failed: {err}");
+                    break;
+                }
+            };
+            if jobs.is_empty() {
+                break;
+            }
+            join_all(jobs.into_iter().map(|job| run(&state, job))).await;
+        }
+    }
+}
+
+/// Runs the job and removes it from the queue, or records the failure so
+/// it's retried later.
+async fn run(state: &ServerState, mut job: Job) {
+    let result = match job.kind {
+        JobKind::Resolve => resolve_url(state, job.article_uuid).await,
+        JobKind::Metadata => enrich_metadata(state, job.article_uuid).await,
+        JobKind::Images => store_images(state, job.article_uuid).await,
+        JobKind::Content => extract_content(state, job.article_uuid).await,
+        JobKind::Snapshot => take_snapshot(state, job.article_uuid).await,
+    };
+
+    let stored = match result {
+        Ok(()) => jobs_query::complete(job.uuid, &state.db).await,
+        Err(err) => {
+            tracing::warn!(
+                "{} job of article {} failed: {err}",
+                job.kind.as_str(),
+                job.article_uuid
+            );
+            job.failed(err, Utc::now());
+            jobs_query::save_failure(job, &state.db).await
+        }
+    };
+    if let Err(err) = stored {
+        tracing::warn!("storing the outcome of a job failed: {err}");
+    }
+}
+
+/// Resolves the url of a short link or AMP page that took too long to
+/// resolve while inserting and queues the enrichment of the article. If the
+/// url leads to an article that is collected already, the article is merged
+/// into that one instead. Urls that can't be resolved are kept.
+async fn resolve_url(state: &ServerState, article_uuid: Uuid) -> Result<(), String> {
+    let db = &state.db;
+    let Some(article) = articles_query::one(db, article_uuid)
+        .await
+        .map_err(|err| err.to_string())?
+    else {
+        return Ok(());
+    };
+
+    let mut resolved = resolve_article(&state.fetcher, article.clone()).await;
+    if resolved.url != article.url {
+        let duplicate_of = articles_query::uuid_by_url(db, &resolved.url, article.uuid)
+            .await
+            .map_err(|err| err.to_string())?;
+        if let Some(duplicate_of) = duplicate_of {
+            return articles_query::merge_duplicate(article.uuid, duplicate_of, db)
+                .await
+                .map_err(|err| err.to_string());
+        }
+
+        let rules = tag_rules_query::all(db)
+            .await
+            .map_err(|err| err.to_string())?;
+        RuleSet::new(rules).apply_to_changed(&article, &mut resolved);
+        articles_query::update(resolved, db)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+
+    jobs_query::enqueue(vec![article.uuid], &JobKind::ENRICHMENT, db)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Articles that were trashed in the meantime aren't enriched. The url is
+/// kept if its canonical url belongs to another collected article. The tag
+/// rules are applied again if the title or url changed.
+async fn enrich_metadata(state: &ServerState, article_uuid: Uuid) -> Result<(), String> {
+    let db = &state.db;
+    let Some(article) = articles_query::one(db, article_uuid)
+        .await
+        .map_err(|err| err.to_string())?
+    else {
+        return Ok(());
+    };
+
+    let mut enriched = fetcher::metadata::enrich(&state.fetcher, article.clone())
+        .await
+        .map_err(|err| err.to_string())?;
+    if enriched.url != article.url
+        && articles_query::is_url_taken(db, &enriched.url, article.uuid)
+            .await
+            .map_err(|err| err.to_string())?
+    {
+        enriched.url = article.url.clone();
+        enriched.original_url = article.original_url.clone();
+    }
+
+    let rules = tag_rules_query::all(db)
+        .await
+        .map_err(|err| err.to_string())?;
+    RuleSet::new(rules).apply_to_changed(&article, &mut enriched);
+
+    if enriched != article {
+        articles_query::update(enriched, db)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+/// Stores the favicon and preview image of the article. Articles without
+/// images are done as well, only an unreachable page is retried.
+async fn store_images(state: &ServerState, article_uuid: Uuid) -> Result<(), String> {
+    let db = &state.db;
+    let Some(article) = articles_query::one(db, article_uuid)
+        .await
+        .map_err(|err| err.to_string())?
+    else {
+        return Ok(());
+    };
+
+    let images = fetcher::images::article_images(&state.fetcher, &article.url)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let favicon = store_image(images.favicon, db).await?;
+    let preview_image = store_image(images.preview, db).await?;
+    articles_query::set_images(article.uuid, favicon, preview_image, db)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Stores the readable content of the article for the reader view together
+/// with its reading time, pages without an article are done without content.
+async fn extract_content(state: &ServerState, article_uuid: Uuid) -> Result<(), String> {
+    let db = &state.db;
+    let Some(article) = articles_query::one(db, article_uuid)
+        .await
+        .map_err(|err| err.to_string())?
+    else {
+        return Ok(());
+    };
+
+    let content = fetcher::reader::fetch_content(&state.fetcher, &article.url)
+        .await
+        .map_err(|err| err.to_string())?;
+    if let Some(content) = content {
+        let reading_time = ReadingTime::of(&content.text);
+        contents_query::store(article.uuid, content, db)
+            .await
+            .map_err(|err| err.to_string())?;
+        articles_query::set_reading_time(article.uuid, reading_time, db)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+/// Stores a snapshot of the article's page for the archive.
+async fn take_snapshot(state: &ServerState, article_uuid: Uuid) -> Result<(), String> {
+    let db = &state.db;
+    let Some(article) = articles_query::one(db, article_uuid)
+        .await
+        .map_err(|err| err.to_string())?
+    else {
+        return Ok(());
+    };
+
+    let snapshot = fetcher::snapshot::take_snapshot(&state.fetcher, &article.url)
+        .await
+        .map_err(|err| err.to_string())?;
+    if let Some(snapshot) = snapshot {
+        snapshots_query::store(article.uuid, snapshot, db)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+/// Stores the image and returns its hash.
+async fn store_image(
+    image: Option<StoredImage>,
+    db: &DatabaseConnection,
+) -> Result<Option<String>, String> {
+    let Some(image) = image else {
+        return Ok(None);
+    };
+    let hash = image.hash.clone();
+    images_query::store(image, db)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(Some(hash))
+}
@@ -1,26 +1,42 @@
 #[cfg(feature = "ssr")]
 use axum::extract::FromRef;
+#[cfg(feature = "ssr")]
+use fetcher::HttpFetcher;
 use leptos::config::LeptosOptions;
 #[cfg(feature = "ssr")]
 use sea_orm::DatabaseConnection;
 
 pub mod app;
 pub mod articles;
+#[cfg(feature = "ssr")]
+pub mod jobs;
 pub mod keycloak;
+pub mod maintenance;
 pub mod routes;
+pub mod subscriptions;
+pub mod tag_rules;
 pub mod utils;
 
 #[cfg(feature = "ssr")]
 #[derive(FromRef, Debug, Clone)]
 pub struct ServerState {
     pub db: DatabaseConnection,
+    pub fetcher: HttpFetcher,
     pub leptos_options: LeptosOptions,
 }
 
 #[cfg(feature = "ssr")]
 impl ServerState {
-    pub fn new(db: DatabaseConnection, leptos_options: LeptosOptions) -> Self {
-        Self { db, leptos_options }
+    pub fn new(
+        db: DatabaseConnection,
+        fetcher: HttpFetcher,
+        leptos_options: LeptosOptions,
+    ) -> Self {
+        Self {
+            db,
+            fetcher,
+            leptos_options,
+        }
     }
 }
 
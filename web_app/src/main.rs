@@ -1,5 +1,15 @@
 #![allow(non_snake_case)]
 
+#[cfg(feature = "ssr")]
+mod archive;
+#[cfg(feature = "ssr")]
+mod caching;
+#[cfg(feature = "ssr")]
+mod export;
+#[cfg(feature = "ssr")]
+mod feeds;
+#[cfg(feature = "ssr")]
+mod images;
 #[cfg(feature = "ssr")]
 mod server_router;
 
@@ -0,0 +1,270 @@
+use std::collections::HashSet;
+
+use domain::{articles::Article, link_health::LinkHealth};
+use leptos::{prelude::*, server};
+use leptos_router::components::A;
+use uuid::Uuid;
+
+use crate::{
+    articles::{ArticleUrl, LinkHealthBadge},
+    keycloak::{AuthClient, ExpectAuth},
+    utils::{Button, CenterColumn, CenteredLoader},
+};
+
+#[component]
+pub fn MaintenancePage() -> impl IntoView {
+    view! {
+        <CenterColumn>
+            <ExpectAuth>
+                <UnhealthyLinks />
+            </ExpectAuth>
+        </CenterColumn>
+    }
+}
+
+/// The articles whose links are broken or moved, selected articles can be
+/// trashed or moved to their new url together.
+#[component]
+fn UnhealthyLinks() -> impl IntoView {
+    let unhealthy_fn = LocalResource::new(get_unhealthy_articles);
+
+    view! {
+        <h2 class="text-3xl p-2">"Maintenance"</h2>
+        <p class="p-2 text-gray-500">
+            "Links are checked in the background, these are broken or redirect elsewhere."
+        </p>
+        <Suspense fallback=CenteredLoader>
+            {move || Suspend::new(async move {
+                match unhealthy_fn.await {
+                    Ok(articles) => {
+                        let articles = RwSignal::new(
+                            articles.into_iter().map(RwSignal::new).collect::<Vec<_>>()
+                        );
+                        let selected = RwSignal::new(HashSet::<Uuid>::new());
+
+                        view! {
+                            <BulkActions articles selected />
+                            <Show when=move || articles.read().is_empty()>
+                                <p class="p-2 text-gray-500">"All links are fine"</p>
+                            </Show>
+                            <div class="flex flex-col gap-6 md:gap-2">
+                                <For each=move || articles.get()
+                                    key=|state| state.read().uuid
+                                    let(article)
+                                >
+                                    <UnhealthyArticle article selected />
+                                </For>
+                            </div>
+                        }.into_any()
+                    }
+                    Err(err) => view! {
+                        <p class="p-2 text-red-600">{err.to_string()}</p>
+                    }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn BulkActions(
+    articles: RwSignal<Vec<RwSignal<Article>>>,
+    selected: RwSignal<HashSet<Uuid>>,
+) -> impl IntoView {
+    let trash = Action::new(move |uuids: &Vec<Uuid>| {
+        let uuids = uuids.clone();
+        async move {
+            if trash_articles(uuids.clone()).await.is_ok() {
+                remove_from_list(articles, selected, &uuids);
+            }
+        }
+    });
+    let follow = Action::new(move |uuids: &Vec<Uuid>| {
+        let uuids = uuids.clone();
+        async move {
+            if let Ok(moved) = follow_redirects(uuids).await {
+                remove_from_list(articles, selected, &moved);
+            }
+        }
+    });
+    let selection = move || selected.get().into_iter().collect::<Vec<_>>();
+    let busy = move || trash.pending().get() || follow.pending().get();
+
+    view! {
+        <div class="flex flex-wrap gap-2 p-2 items-center">
+            <button on:click=move |_| {
+                let all = articles.read().iter().map(|a| a.read().uuid).collect();
+                selected.set(all);
+            }>
+                <Button>"select all"</Button>
+            </button>
+            <button on:click=move |_| selected.set(HashSet::new())>
+                <Button>"select none"</Button>
+            </button>
+            <Show when=move || !busy() fallback=CenteredLoader>
+                <button title="Replace the urls of the selected moved articles by their new url"
+                    on:click=move |_| { follow.dispatch(selection()); }>
+                    <Button>"use new urls"</Button>
+                </button>
+                <button on:click=move |_| { trash.dispatch(selection()); }>
+                    <Button>"trash selected"</Button>
+                </button>
+            </Show>
+            <span class="text-gray-500">
+                {move || format!("{} selected", selected.read().len())}
+            </span>
+        </div>
+    }
+}
+
+#[component]
+fn UnhealthyArticle(
+    article: RwSignal<Article>,
+    selected: RwSignal<HashSet<Uuid>>,
+) -> impl IntoView {
+    let uuid = article.read_untracked().uuid;
+    let health = Signal::derive(move || article.get().link_health);
+
+    view! {
+        <label class="flex gap-2 p-2 items-start">
+            <input type="checkbox" class="mt-2"
+                prop:checked=move || selected.read().contains(&uuid)
+                on:change=move |_| selected.update(|selected| {
+                    if !selected.remove(&uuid) {
+                        selected.insert(uuid);
+                    }
+                }) />
+            <div class="flex flex-col gap-1 overflow-hidden">
+                <A href=format!("/articles/{uuid}")>
+                    <h3 class="text-2xl text-wrap">{move || article.get().title}</h3>
+                </A>
+                <ArticleUrl url=Signal::derive(move || article.get().url)
+                    add_classes="wrap-break-word" />
+                <div class="flex gap-2">
+                    <LinkHealthBadge health />
+                    <span class="text-gray-500 wrap-break-word">
+                        {move || health.get().as_ref().map(LinkHealth::reason)}
+                    </span>
+                </div>
+            </div>
+        </label>
+    }
+}
+
+fn remove_from_list(
+    articles: RwSignal<Vec<RwSignal<Article>>>,
+    selected: RwSignal<HashSet<Uuid>>,
+    uuids: &[Uuid],
+) {
+    articles.update(|articles| {
+        let _ = articles
+            .extract_if(.., |a| uuids.contains(&a.read().uuid))
+            .count();
+    });
+    selected.update(|selected| selected.retain(|uuid| !uuids.contains(uuid)));
+}
+
+/// Checks the links of all articles forever, each link once within the
+/// interval. The interval can be set with `LINK_CHECK_INTERVAL_DAYS` and
+/// defaults to a week, changed urls are checked within an hour.
+#[cfg(feature = "ssr")]
+pub async fn check_links_periodically(state: crate::ServerState) {
+    use database::link_checks_query;
+    use fetcher::link_health::check_links;
+    use std::env;
+
+    /// How many links are taken from the database at once
+    const BATCH_SIZE: u64 = 100;
+
+    let interval = env::var("LINK_CHECK_INTERVAL_DAYS")
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .map(chrono::Duration::days)
+        .unwrap_or(chrono::Duration::days(7));
+    let mut ticks = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+
+    loop {
+        ticks.tick().await;
+        let checked_before = chrono::Utc::now() - interval;
+        // links are gone through once per tick, failed ones wait for the next
+        let mut after = None;
+        'batches: loop {
+            let due = link_checks_query::due(&state.db, checked_before, after, BATCH_SIZE).await;
+            let links = match due {
+                Ok(links) => links,
+                Err(err) => {
+                    tracing::warn!("loading the links to check failed: {err}");
+                    break;
+                }
+            };
+            let Some((last, _)) = links.last() else {
+                break;
+            };
+            after = Some(*last);
+
+            for (uuid, check) in check_links(&state.fetcher, links).await {
+                if let Err(err) = link_checks_query::store(uuid, check, &state.db).await {
+                    tracing::warn!("storing the link check of article {uuid} failed: {err}");
+                    break 'batches;
+                }
+            }
+        }
+    }
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn get_unhealthy_articles() -> Result<Vec<Article>, ServerFnError> {
+    use crate::ServerState;
+    use database::link_checks_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(link_checks_query::unhealthy(&state.db).await?)
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn trash_articles(article_uuids: Vec<Uuid>) -> Result<(), ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+
+    let state = expect_context::<ServerState>();
+    for uuid in article_uuids {
+        articles_query::trash(uuid, &state.db).await?;
+    }
+    Ok(())
+}
+
+/// Sets the url of each moved article to where it redirects to and returns
+/// the articles that were changed. Articles whose new url is already
+/// collected are left as they are.
+#[server(
+    client = AuthClient
+)]
+async fn follow_redirects(article_uuids: Vec<Uuid>) -> Result<Vec<Uuid>, ServerFnError> {
+    use crate::ServerState;
+    use database::articles_query;
+    use domain::urls::normalize_url;
+
+    let state = expect_context::<ServerState>();
+    let mut moved = vec![];
+    for uuid in article_uuids {
+        let Some(mut article) = articles_query::one(&state.db, uuid).await? else {
+            continue;
+        };
+        let Some(LinkHealth::Moved { to }) = article.link_health.take() else {
+            continue;
+        };
+        if normalize_url(&to).is_err() || articles_query::is_url_taken(&state.db, &to, uuid).await?
+        {
+            continue;
+        }
+
+        article.url = to;
+        articles_query::update(article, &state.db).await?;
+        moved.push(uuid);
+    }
+    Ok(moved)
+}
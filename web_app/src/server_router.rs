@@ -16,6 +16,7 @@ use axum_keycloak_auth::{
     instance::KeycloakConfig,
     NonEmpty, PassthroughMode, Url,
 };
+use fetcher::HttpFetcher;
 use leptos::{config::LeptosOptions, error::Errors, prelude::*, view};
 use leptos_axum::{generate_route_list, handle_server_fns_with_context, LeptosRoutes};
 use sea_orm::{DatabaseConnection, DbErr};
@@ -28,9 +29,19 @@ use tracing::{info, warn};
 use web_app::keycloak::KeycloakInfo;
 use web_app::{
     app::{shell, App},
+    jobs::run_jobs_periodically,
+    maintenance::check_links_periodically,
+    subscriptions::poll_feeds_periodically,
     ServerState,
 };
 
+use crate::{
+    archive::serve_archive,
+    export::export_articles,
+    feeds::{atom_feed, json_feed_page, rss_feed},
+    images::serve_image,
+};
+
 pub async fn file_and_error_handler(
     uri: Uri,
     State(state): State<ServerState>,
@@ -122,7 +133,12 @@ pub async fn leptos_routes_handler(
 
 pub async fn router(leptos_options: LeptosOptions) -> IntoMakeService<Router> {
     let connection = setup_database().await.unwrap();
-    let state = ServerState::new(connection, leptos_options);
+    let fetcher = HttpFetcher::with_defaults().unwrap();
+    let state = ServerState::new(connection, fetcher, leptos_options);
+
+    tokio::spawn(poll_feeds_periodically(state.clone()));
+    tokio::spawn(run_jobs_periodically(state.clone()));
+    tokio::spawn(check_links_periodically(state.clone()));
 
     let client_router = Router::new()
         .leptos_routes_with_handler(generate_route_list(App), get(leptos_routes_handler));
@@ -136,7 +152,13 @@ pub async fn router(leptos_options: LeptosOptions) -> IntoMakeService<Router> {
         .route(
             "/public/api/{*fn_name}",
             get(server_fn_handler).post(server_fn_handler),
-        );
+        )
+        .route("/export/{format}", get(export_articles))
+        .route("/feed.rss", get(rss_feed))
+        .route("/feed.atom", get(atom_feed))
+        .route("/feed.json", get(json_feed_page))
+        .route("/images/{hash}", get(serve_image))
+        .route("/articles/{uuid}/archive", get(serve_archive));
 
     Router::new()
         .merge(client_router)
@@ -0,0 +1,304 @@
+use domain::{feeds::Feed, submission::SubmissionReport};
+use leptos::{prelude::*, server};
+use uuid::Uuid;
+
+use crate::{
+    articles::{edit::Report, tags::TagList, ArticleDate, ArticleUrl},
+    keycloak::{AuthClient, ExpectAuth},
+    utils::{Button, CenterColumn, CenteredLoader},
+};
+
+#[component]
+pub fn FeedsPage() -> impl IntoView {
+    view! {
+        <CenterColumn>
+            <ExpectAuth>
+                <FeedList />
+            </ExpectAuth>
+        </CenterColumn>
+    }
+}
+
+#[component]
+fn FeedList() -> impl IntoView {
+    let feeds_fn = LocalResource::new(get_feeds);
+
+    view! {
+        <h2 class="text-3xl p-2">"Feeds"</h2>
+        <Suspense fallback=CenteredLoader>
+            {move || Suspend::new(async move {
+                match feeds_fn.await {
+                    Ok(feeds) => {
+                        let feeds = RwSignal::new(
+                            feeds.into_iter().map(RwSignal::new).collect::<Vec<_>>()
+                        );
+                        let report = RwSignal::new(None::<SubmissionReport>);
+
+                        view! {
+                            <SubscribeForm feeds />
+                            <Show when=move || feeds.read().is_empty()>
+                                <p class="p-2 text-gray-500">"No feeds subscribed to yet"</p>
+                            </Show>
+                            <div class="flex flex-col gap-6 md:gap-2">
+                                <For each=move || feeds.get()
+                                    key=|state| state.read().uuid
+                                    let(feed)
+                                >
+                                    <FeedEntry feed feeds report />
+                                </For>
+                            </div>
+                            {move || report.get().map(|report| view! { <Report report /> })}
+                        }.into_any()
+                    }
+                    Err(err) => view! {
+                        <p class="p-2 text-red-600">{err.to_string()}</p>
+                    }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn SubscribeForm(feeds: RwSignal<Vec<RwSignal<Feed>>>) -> impl IntoView {
+    let url = RwSignal::new(String::new());
+    let tags = RwSignal::new(String::new());
+    let subscribe = Action::new(move |(url, tags): &(String, String)| {
+        let (url, tags) = (url.clone(), tags.clone());
+        async move {
+            let feed = subscribe_to_feed(url, tags).await?;
+            feeds.update(|feeds| feeds.push(RwSignal::new(feed)));
+            Ok::<_, ServerFnError>(())
+        }
+    });
+
+    Effect::watch(
+        move || subscribe.value().get(),
+        move |value, _, _| {
+            if let Some(Ok(())) = value {
+                url.set(String::new());
+            }
+        },
+        false,
+    );
+
+    view! {
+        <div class="flex flex-col gap-2 p-2">
+            <label class="underline" for="feed-url">
+                "Feed url (RSS or Atom)"
+            </label>
+            <input type="url" id="feed-url"
+                bind:value=url
+                placeholder="https://blog.rust-lang.org/feed.xml"
+                class="border-1 border-gray-200 px-1" />
+            <label class="underline" for="feed-tags">
+                "Tags for new articles (comma separated)"
+            </label>
+            <input type="text" id="feed-tags"
+                bind:value=tags
+                placeholder="rust, blogs"
+                class="border-1 border-gray-200 px-1" />
+            {move || subscribe.value().get().and_then(Result::err).map(|err| view! {
+                <p class="text-red-700">{err.to_string()}</p>
+            })}
+            <div class="flex flex-row-reverse">
+                <Show when=move || !subscribe.pending().get() fallback=CenteredLoader>
+                    <button on:click=move |_| {
+                        subscribe.dispatch((url.get(), tags.get()));
+                    }>
+                        <Button>"Subscribe"</Button>
+                    </button>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn FeedEntry(
+    feed: RwSignal<Feed>,
+    feeds: RwSignal<Vec<RwSignal<Feed>>>,
+    report: RwSignal<Option<SubmissionReport>>,
+) -> impl IntoView {
+    let poll = Action::new(move |uuid: &Uuid| {
+        let uuid = *uuid;
+        async move {
+            if let Ok((polled, polled_report)) = poll_feed_now(uuid).await {
+                feed.set(polled);
+                report.set(Some(polled_report));
+            }
+        }
+    });
+    let unsubscribe = Action::new(move |uuid: &Uuid| {
+        let uuid = *uuid;
+        async move {
+            if unsubscribe_from_feed(uuid).await.is_ok() {
+                feeds.update(|feeds| {
+                    let _ = feeds.extract_if(.., |f| f.read().uuid.eq(&uuid)).count();
+                });
+            }
+        }
+    });
+
+    view! {
+        <div class="flex flex-col gap-1 p-2 relative overflow-hidden">
+            <h3 class="text-2xl text-wrap">
+                {move || feed.get().title.unwrap_or_else(|| feed.get().url)}
+            </h3>
+            <ArticleUrl url=Signal::derive(move || feed.get().url) add_classes="wrap-break-word" />
+            <div class="flex gap-2">
+                {move || match feed.get().last_polled_at {
+                    Some(polled_at) => view! {
+                        <ArticleDate date=Signal::stored(polled_at) label="polled " />
+                    }.into_any(),
+                    None => view! {
+                        <span class="text-gray-500">"not polled yet"</span>
+                    }.into_any(),
+                }}
+                <TagList tags=Signal::derive(move || feed.get().tags) />
+            </div>
+            {move || feed.get().last_error.map(|err| view! {
+                <p class="text-red-700 wrap-break-word">{err}</p>
+            })}
+            <div class="flex flex-row-reverse gap-2">
+                <button on:click=move |_| { unsubscribe.dispatch(feed.read().uuid); }>
+                    <Button>"unsubscribe"</Button>
+                </button>
+                <Show when=move || !poll.pending().get() fallback=CenteredLoader>
+                    <button on:click=move |_| { poll.dispatch(feed.read().uuid); }>
+                        <Button>"poll now"</Button>
+                    </button>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+/// Polls the feed and inserts its new entries through the same path as
+/// `add_articles`. A feed that can't be fetched or parsed is not an error,
+/// the reason is stored with the feed and shown on the feeds page.
+#[cfg(feature = "ssr")]
+pub async fn poll_and_store<F>(
+    fetcher: &F,
+    feed: &Feed,
+    db: &sea_orm::DatabaseConnection,
+) -> Result<SubmissionReport, sea_orm::DbErr>
+where
+    F: fetcher::Fetch,
+{
+    use crate::jobs::insert_and_enrich;
+    use database::feeds_query;
+    use fetcher::subscriptions::{poll, PolledFeed};
+
+    match poll(fetcher, feed).await {
+        Ok(PolledFeed { title, entries }) => {
+            let report = insert_and_enrich(entries, fetcher, db).await?;
+            feeds_query::set_polled(feed.uuid, title, None, db).await?;
+            Ok(report)
+        }
+        Err(err) => {
+            tracing::warn!("polling feed {} failed: {err}", feed.url);
+            feeds_query::set_polled(feed.uuid, None, Some(err.to_string()), db).await?;
+            Ok(SubmissionReport::default())
+        }
+    }
+}
+
+/// Polls every feed that is due, forever. How often a feed is polled can be
+/// set with `FEED_POLL_INTERVAL_MINUTES` and defaults to 30 minutes, new
+/// subscriptions are picked up within a minute.
+#[cfg(feature = "ssr")]
+pub async fn poll_feeds_periodically(state: crate::ServerState) {
+    use database::feeds_query;
+    use std::env;
+
+    let interval = env::var("FEED_POLL_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|minutes| minutes.parse().ok())
+        .map(chrono::Duration::minutes)
+        .unwrap_or(chrono::Duration::minutes(30));
+    let mut ticks = tokio::time::interval(std::time::Duration::from_secs(60));
+
+    loop {
+        ticks.tick().await;
+        let feeds = match feeds_query::all(&state.db).await {
+            Ok(feeds) => feeds,
+            Err(err) => {
+                tracing::warn!("loading the feeds to poll failed: {err}");
+                continue;
+            }
+        };
+
+        let now = chrono::Utc::now();
+        for feed in feeds.iter().filter(|feed| feed.is_due(now, interval)) {
+            if let Err(err) = poll_and_store(&state.fetcher, feed, &state.db).await {
+                tracing::warn!("storing the entries of feed {} failed: {err}", feed.url);
+            }
+        }
+    }
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn get_feeds() -> Result<Vec<Feed>, ServerFnError> {
+    use crate::ServerState;
+    use database::feeds_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(feeds_query::all(&state.db).await?)
+}
+
+/// Subscribes to the feed and polls it right away, so the title of the feed
+/// or why it can't be read is known immediately.
+#[server(
+    client = AuthClient
+)]
+async fn subscribe_to_feed(url: String, tags: String) -> Result<Feed, ServerFnError> {
+    use crate::ServerState;
+    use database::feeds_query;
+    use domain::{tags::parse_tag_list, urls::normalize_url};
+
+    let url = url.trim().to_string();
+    normalize_url(&url).map_err(|err| ServerFnError::new(format!("invalid url: {err}")))?;
+
+    let state = expect_context::<ServerState>();
+    let feed = feeds_query::insert(Feed::new(url, parse_tag_list(&tags)), &state.db).await?;
+    poll_and_store(&state.fetcher, &feed, &state.db).await?;
+
+    feeds_query::one(&state.db, feed.uuid)
+        .await?
+        .ok_or_else(|| ServerFnError::new("feed was deleted while polling it"))
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn poll_feed_now(feed_uuid: Uuid) -> Result<(Feed, SubmissionReport), ServerFnError> {
+    use crate::ServerState;
+    use database::feeds_query;
+
+    let state = expect_context::<ServerState>();
+    let not_found = || ServerFnError::new("feed not found");
+    let feed = feeds_query::one(&state.db, feed_uuid)
+        .await?
+        .ok_or_else(not_found)?;
+    let report = poll_and_store(&state.fetcher, &feed, &state.db).await?;
+
+    let feed = feeds_query::one(&state.db, feed_uuid)
+        .await?
+        .ok_or_else(not_found)?;
+    Ok((feed, report))
+}
+
+/// Only removes the subscription, the articles it added are kept.
+#[server(
+    client = AuthClient
+)]
+async fn unsubscribe_from_feed(feed_uuid: Uuid) -> Result<(), ServerFnError> {
+    use crate::ServerState;
+    use database::feeds_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(feeds_query::delete(feed_uuid, &state.db).await?)
+}
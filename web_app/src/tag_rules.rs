@@ -0,0 +1,256 @@
+use domain::tag_rules::{RuleKind, TagRule};
+use leptos::{prelude::*, server};
+use uuid::Uuid;
+
+use crate::{
+    articles::tags::TagList,
+    keycloak::{AuthClient, ExpectAuth},
+    utils::{Button, CenterColumn, CenteredLoader},
+};
+
+#[component]
+pub fn TagRulesPage() -> impl IntoView {
+    view! {
+        <CenterColumn>
+            <ExpectAuth>
+                <RuleList />
+            </ExpectAuth>
+        </CenterColumn>
+    }
+}
+
+#[component]
+fn RuleList() -> impl IntoView {
+    let rules_fn = LocalResource::new(get_tag_rules);
+
+    view! {
+        <h2 class="text-3xl p-2">"Tag Rules"</h2>
+        <p class="p-2 text-gray-500">
+            "New articles get the tags of every rule they match."
+        </p>
+        <Suspense fallback=CenteredLoader>
+            {move || Suspend::new(async move {
+                match rules_fn.await {
+                    Ok(rules) => {
+                        let rules = RwSignal::new(
+                            rules.into_iter().map(RwSignal::new).collect::<Vec<_>>()
+                        );
+
+                        view! {
+                            <AddRuleForm rules />
+                            <ReapplyRules />
+                            <Show when=move || rules.read().is_empty()>
+                                <p class="p-2 text-gray-500">"No rules yet"</p>
+                            </Show>
+                            <div class="flex flex-col gap-6 md:gap-2">
+                                <For each=move || rules.get()
+                                    key=|state| state.read().uuid
+                                    let(rule)
+                                >
+                                    <RuleEntry rule rules />
+                                </For>
+                            </div>
+                        }.into_any()
+                    }
+                    Err(err) => view! {
+                        <p class="p-2 text-red-600">{err.to_string()}</p>
+                    }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[component]
+fn AddRuleForm(rules: RwSignal<Vec<RwSignal<TagRule>>>) -> impl IntoView {
+    let kind = RwSignal::new(RuleKind::Host);
+    let pattern = RwSignal::new(String::new());
+    let tags = RwSignal::new(String::new());
+    let add = Action::new(move |(kind, pattern, tags): &(RuleKind, String, String)| {
+        let (kind, pattern, tags) = (*kind, pattern.clone(), tags.clone());
+        async move {
+            let rule = add_tag_rule(kind, pattern, tags).await?;
+            rules.update(|rules| rules.push(RwSignal::new(rule)));
+            Ok::<_, ServerFnError>(())
+        }
+    });
+
+    Effect::watch(
+        move || add.value().get(),
+        move |value, _, _| {
+            if let Some(Ok(())) = value {
+                pattern.set(String::new());
+            }
+        },
+        false,
+    );
+
+    let placeholder = move || match kind.get() {
+        RuleKind::Host => "github.com",
+        RuleKind::PathGlob => "/blog/**",
+        RuleKind::TitleRegex => r"\brust\b",
+    };
+
+    view! {
+        <div class="flex flex-col gap-2 p-2">
+            <label class="underline" for="rule-pattern">"Match"</label>
+            <div class="flex gap-2">
+                <select class="border-1 border-gray-200 px-1"
+                    on:change:target=move |ev| {
+                        if let Some(selected) = RuleKind::from_name(&ev.target().value()) {
+                            kind.set(selected);
+                        }
+                    }>
+                    {RuleKind::ALL.into_iter().map(|option| view! {
+                        <option value=option.as_str()
+                            selected=move || kind.get() == option>
+                            {rule_kind_label(option)}
+                        </option>
+                    }).collect_view()}
+                </select>
+                <input type="text" id="rule-pattern"
+                    bind:value=pattern
+                    placeholder=placeholder
+                    class="border-1 border-gray-200 px-1 grow" />
+            </div>
+            <label class="underline" for="rule-tags">
+                "Tags (comma separated)"
+            </label>
+            <input type="text" id="rule-tags"
+                bind:value=tags
+                placeholder="code"
+                class="border-1 border-gray-200 px-1" />
+            {move || add.value().get().and_then(Result::err).map(|err| view! {
+                <p class="text-red-700">{err.to_string()}</p>
+            })}
+            <div class="flex flex-row-reverse">
+                <Show when=move || !add.pending().get() fallback=CenteredLoader>
+                    <button on:click=move |_| {
+                        add.dispatch((kind.get(), pattern.get(), tags.get()));
+                    }>
+                        <Button>"Add rule"</Button>
+                    </button>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ReapplyRules() -> impl IntoView {
+    let reapply = Action::new(move |_: &()| reapply_tag_rules());
+
+    view! {
+        <div class="flex gap-2 p-2 items-center">
+            <Show when=move || !reapply.pending().get() fallback=CenteredLoader>
+                <button title="Add the tags of matching rules to the articles already collected"
+                    on:click=move |_| { reapply.dispatch(()); }>
+                    <Button>"re-apply rules to existing articles"</Button>
+                </button>
+            </Show>
+            {move || reapply.value().get().map(|result| match result {
+                Ok(count) => view! {
+                    <span class="text-gray-500">{format!("{count} articles got new tags")}</span>
+                }.into_any(),
+                Err(err) => view! {
+                    <span class="text-red-700">{err.to_string()}</span>
+                }.into_any(),
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn RuleEntry(rule: RwSignal<TagRule>, rules: RwSignal<Vec<RwSignal<TagRule>>>) -> impl IntoView {
+    let delete = Action::new(move |uuid: &Uuid| {
+        let uuid = *uuid;
+        async move {
+            if delete_tag_rule(uuid).await.is_ok() {
+                rules.update(|rules| {
+                    let _ = rules.extract_if(.., |r| r.read().uuid.eq(&uuid)).count();
+                });
+            }
+        }
+    });
+
+    view! {
+        <div class="flex gap-2 p-2 items-center justify-between overflow-hidden">
+            <div class="flex flex-col gap-1 overflow-hidden">
+                <p class="wrap-break-word">
+                    <span class="text-gray-500">
+                        {move || rule_kind_label(rule.read().kind)} " "
+                    </span>
+                    <code>{move || rule.get().pattern}</code>
+                </p>
+                <TagList tags=Signal::derive(move || rule.get().tags) />
+            </div>
+            <button on:click=move |_| { delete.dispatch(rule.read().uuid); }>
+                <Button>"delete"</Button>
+            </button>
+        </div>
+    }
+}
+
+fn rule_kind_label(kind: RuleKind) -> &'static str {
+    match kind {
+        RuleKind::Host => "host",
+        RuleKind::PathGlob => "path glob",
+        RuleKind::TitleRegex => "title regex",
+    }
+}
+
+#[server(
+    client = AuthClient
+)]
+async fn get_tag_rules() -> Result<Vec<TagRule>, ServerFnError> {
+    use crate::ServerState;
+    use database::tag_rules_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(tag_rules_query::all(&state.db).await?)
+}
+
+/// The rule only applies to articles added from now on, existing articles
+/// are tagged by [`reapply_tag_rules`].
+#[server(
+    client = AuthClient
+)]
+async fn add_tag_rule(
+    kind: RuleKind,
+    pattern: String,
+    tags: String,
+) -> Result<TagRule, ServerFnError> {
+    use crate::ServerState;
+    use database::tag_rules_query;
+    use domain::tags::parse_tag_list;
+
+    let rule = TagRule::new(kind, &pattern, parse_tag_list(&tags))
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+
+    let state = expect_context::<ServerState>();
+    Ok(tag_rules_query::insert(rule, &state.db).await?)
+}
+
+/// Only removes the rule, the tags it added are kept.
+#[server(
+    client = AuthClient
+)]
+async fn delete_tag_rule(rule_uuid: Uuid) -> Result<(), ServerFnError> {
+    use crate::ServerState;
+    use database::tag_rules_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(tag_rules_query::delete(rule_uuid, &state.db).await?)
+}
+
+/// Returns how many articles got new tags.
+#[server(
+    client = AuthClient
+)]
+async fn reapply_tag_rules() -> Result<usize, ServerFnError> {
+    use crate::ServerState;
+    use database::tag_rules_query;
+
+    let state = expect_context::<ServerState>();
+    Ok(tag_rules_query::reapply(&state.db).await?)
+}